@@ -61,8 +61,10 @@ export async function activate(context: ExtensionContext) {
     // Register the server for plain text documents
     documentSelector: [{ scheme: "file", language: "asp" }],
     synchronize: {
-      // Notify the server about file changes to '.clientrc files contained in the workspace
-      fileEvents: workspace.createFileSystemWatcher("**/.clientrc"),
+      // Notify the server about changes to the settings of the language server
+      configurationSection: "asp-language-server",
+      // Notify the server about changes to the project file and to encodings that are not open
+      fileEvents: workspace.createFileSystemWatcher("**/{asp-project.toml,*.lp}"),
     },
     traceOutputChannel,
   };
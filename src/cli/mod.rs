@@ -0,0 +1,379 @@
+use std::{
+    collections::HashSet,
+    env,
+    ffi::OsStr,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Url};
+
+use crate::{configuration::ServerConfiguration, workspace::Workspace};
+
+use self::report::OutputFormat;
+
+pub mod report;
+
+/**
+ * The exit code if no errors or warnings were found
+ */
+pub const EXIT_SUCCESS: i32 = 0;
+/**
+ * The exit code if errors or warnings were found
+ */
+pub const EXIT_PROBLEMS: i32 = 1;
+/**
+ * The exit code if the arguments are invalid or a file could not be read
+ */
+pub const EXIT_FAILURE: i32 = 2;
+
+const LINT_USAGE: &str =
+    "Usage: asp-language-server lint [options] <files, directories or globs>...
+
+Runs the diagnostics of the language server on the files and prints them like clingo does.
+Directories are searched for .lp files, an asp-project.toml in the current directory is respected.
+
+Options:
+  -c, --const <name>=<value>   Replace the value of a constant, like clingo does
+  -f, --format <format>        Print the diagnostics as text, json or sarif, defaults to text
+  -h, --help                   Print this help";
+
+/**
+ * The arguments of the lint subcommand
+ */
+#[derive(Debug, PartialEq)]
+pub struct LintOptions {
+    pub paths: Vec<String>,
+    pub constants: Vec<(String, String)>,
+    pub format: OutputFormat,
+    pub help: bool,
+}
+
+impl Default for LintOptions {
+    fn default() -> Self {
+        LintOptions {
+            paths: Vec::new(),
+            constants: Vec::new(),
+            format: OutputFormat::Text,
+            help: false,
+        }
+    }
+}
+
+impl LintOptions {
+    /**
+     * Parse the arguments that follow the lint subcommand
+     */
+    pub fn parse(arguments: &[String]) -> Result<LintOptions, String> {
+        let mut options = LintOptions::default();
+        let mut arguments = arguments.iter();
+
+        while let Some(argument) = arguments.next() {
+            match argument.as_str() {
+                "-h" | "--help" => options.help = true,
+                "-c" | "--const" => {
+                    let value = arguments
+                        .next()
+                        .ok_or_else(|| format!("Missing value for {}", argument))?;
+                    options.constants.push(parse_constant(value)?);
+                }
+                "-f" | "--format" => {
+                    let value = arguments
+                        .next()
+                        .ok_or_else(|| format!("Missing value for {}", argument))?;
+                    options.format = parse_format(value)?;
+                }
+                _ if argument.starts_with("--format=") => {
+                    options.format = parse_format(&argument["--format=".len()..])?;
+                }
+                _ if argument.starts_with("--const=")
+                    || (argument.starts_with("-c") && argument.len() > 2) =>
+                {
+                    options.constants.push(parse_constant(argument)?);
+                }
+                _ if argument.starts_with('-') && argument.len() > 1 => {
+                    return Err(format!("Unknown option '{}'", argument));
+                }
+                _ => options.paths.push(argument.clone()),
+            }
+        }
+
+        if options.paths.is_empty() && !options.help {
+            return Err("No files to lint".to_string());
+        }
+
+        Ok(options)
+    }
+}
+
+fn parse_constant(argument: &str) -> Result<(String, String), String> {
+    ServerConfiguration::parse_constant_argument(argument)
+        .ok_or_else(|| format!("Invalid constant '{}', expected <name>=<value>", argument))
+}
+
+fn parse_format(name: &str) -> Result<OutputFormat, String> {
+    OutputFormat::from_name(name)
+        .ok_or_else(|| format!("Unknown format '{}', expected text, json or sarif", name))
+}
+
+/**
+ * Find the files the paths refer to. Directories are searched for .lp files, other paths can be globs
+ */
+pub fn collect_files(paths: &[String]) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        if Path::new(path).is_dir() {
+            collect_directory(&normalize_path(Path::new(path)), &mut files)
+                .map_err(|error| format!("Could not read {}: {}", path, error))?;
+            continue;
+        }
+
+        let matches =
+            glob::glob(path).map_err(|error| format!("Invalid glob '{}': {}", path, error))?;
+        let mut found = false;
+        for entry in matches {
+            let entry = entry.map_err(|error| error.to_string())?;
+            if entry.is_file() {
+                found = true;
+                files.push(normalize_path(&entry));
+            }
+        }
+
+        if !found {
+            return Err(format!("'{}' does not match any file", path));
+        }
+    }
+
+    // The same file can be given in several ways, like ./a.lp and a.lp, only its first appearance is kept
+    let mut seen = HashSet::new();
+    files.retain(|file| seen.insert(fs::canonicalize(file).unwrap_or_else(|_| file.clone())));
+
+    Ok(files)
+}
+
+/**
+ * Remove the . components of a path, so ./a.lp is shown as a.lp
+ */
+fn normalize_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| *component != Component::CurDir)
+        .collect()
+}
+
+fn collect_directory(directory: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(directory)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<_>>()?;
+    entries.sort();
+
+    for entry in entries {
+        if entry.is_dir() {
+            collect_directory(&entry, files)?;
+        } else if entry.extension() == Some(OsStr::new("lp")) {
+            files.push(entry);
+        }
+    }
+
+    Ok(())
+}
+
+/**
+ * Analyze the files like the language server does for open documents and return the diagnostics of each file.
+ * The files they include and the files of their groups in the project are loaded as well
+ */
+pub fn lint_files(
+    files: &[PathBuf],
+    workspace: &Workspace,
+) -> Result<Vec<(PathBuf, Vec<Diagnostic>)>, String> {
+    let current_directory = env::current_dir().map_err(|error| error.to_string())?;
+
+    let mut uris = Vec::new();
+    for file in files {
+        let uri = Url::from_file_path(current_directory.join(file))
+            .map_err(|_| format!("Invalid path {}", file.display()))?;
+        if !workspace.open_file(&uri) {
+            return Err(format!("Could not read {}", file.display()));
+        }
+        uris.push(uri);
+    }
+
+    let mut results = Vec::new();
+    for (file, uri) in files.iter().zip(uris) {
+        let document = workspace
+            .documents
+            .get(uri.as_str())
+            .unwrap()
+            .value()
+            .clone();
+        let mut diagnostics = workspace.diagnose(document, u32::MAX);
+        diagnostics.sort_by_key(|diagnostic| {
+            (
+                diagnostic.range.start.line,
+                diagnostic.range.start.character,
+            )
+        });
+
+        results.push((file.clone(), diagnostics));
+    }
+
+    Ok(results)
+}
+
+/**
+ * If any of the diagnostics should fail the lint run, hints and information do not
+ */
+pub fn has_problems(results: &[(PathBuf, Vec<Diagnostic>)]) -> bool {
+    results
+        .iter()
+        .flat_map(|(_, diagnostics)| diagnostics)
+        .any(|diagnostic| {
+            matches!(
+                diagnostic.severity,
+                None | Some(DiagnosticSeverity::ERROR) | Some(DiagnosticSeverity::WARNING)
+            )
+        })
+}
+
+/**
+ * Run the lint subcommand with the arguments that follow it and return the exit code
+ */
+pub fn run_lint(arguments: &[String]) -> i32 {
+    let options = match LintOptions::parse(arguments) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, LINT_USAGE);
+            return EXIT_FAILURE;
+        }
+    };
+    if options.help {
+        println!("{}", LINT_USAGE);
+        return EXIT_SUCCESS;
+    }
+
+    let mut configuration = ServerConfiguration::default();
+    configuration.constants.extend(options.constants);
+    let workspace = Workspace::new(configuration);
+
+    // The project in the current directory is used like the project of a workspace folder
+    let folders: Vec<Url> = env::current_dir()
+        .ok()
+        .and_then(|directory| Url::from_directory_path(directory).ok())
+        .into_iter()
+        .collect();
+    let errors = workspace.load_projects(&folders);
+    if !errors.is_empty() {
+        for error in errors {
+            eprintln!("error: {}", error);
+        }
+        return EXIT_FAILURE;
+    }
+
+    let results =
+        match collect_files(&options.paths).and_then(|files| lint_files(&files, &workspace)) {
+            Ok(results) => results,
+            Err(error) => {
+                eprintln!("error: {}", error);
+                return EXIT_FAILURE;
+            }
+        };
+
+    print!("{}", options.format.format(&results));
+
+    if has_problems(&results) {
+        EXIT_PROBLEMS
+    } else {
+        EXIT_SUCCESS
+    }
+}
+
+#[cfg(test)]
+use tower_lsp::lsp_types::NumberOrString;
+
+#[cfg(test)]
+use crate::test_utils::TestDirectory;
+
+#[cfg(test)]
+fn create_test_arguments(arguments: &[&str]) -> Vec<String> {
+    arguments
+        .iter()
+        .map(|argument| argument.to_string())
+        .collect()
+}
+
+#[test]
+fn lint_arguments_should_be_parsed() {
+    let options = LintOptions::parse(&create_test_arguments(&[
+        "-c",
+        "n=5",
+        "--const=m=2",
+        "*.lp",
+        "-f",
+        "sarif",
+        "enc",
+    ]))
+    .unwrap();
+
+    assert_eq!(options.paths, vec!["*.lp", "enc"]);
+    assert_eq!(options.format, OutputFormat::Sarif);
+    assert_eq!(
+        options.constants,
+        vec![
+            ("n".to_string(), "5".to_string()),
+            ("m".to_string(), "2".to_string())
+        ]
+    );
+
+    assert!(LintOptions::parse(&create_test_arguments(&[])).is_err());
+    assert!(LintOptions::parse(&create_test_arguments(&["--fix", "a.lp"])).is_err());
+    assert!(LintOptions::parse(&create_test_arguments(&["--format=xml", "a.lp"])).is_err());
+    assert!(LintOptions::parse(&create_test_arguments(&["-c", "n", "a.lp"])).is_err());
+    assert!(
+        LintOptions::parse(&create_test_arguments(&["--help"]))
+            .unwrap()
+            .help
+    );
+}
+
+#[test]
+fn files_should_only_be_collected_once() {
+    let directory = TestDirectory::new();
+    directory.write("a.lp", "a.");
+    directory.write("b.lp", "b.");
+
+    let files = collect_files(&[
+        directory.join("b.lp").display().to_string(),
+        directory.join("*.lp").display().to_string(),
+        directory.join(".").join("a.lp").display().to_string(),
+    ])
+    .unwrap();
+    assert_eq!(files, vec![directory.join("b.lp"), directory.join("a.lp")]);
+}
+
+#[test]
+fn files_should_be_linted_with_their_includes() {
+    let directory = TestDirectory::new();
+    directory.write("enc/base.lp", "b(1).");
+    directory.write(
+        "main.lp",
+        "#include \"enc/base.lp\".\na(X) :- b(X).\nc :- d.",
+    );
+    directory.write("enc/notes.txt", "");
+
+    let files = collect_files(&[directory.path.display().to_string()]).unwrap();
+    assert_eq!(
+        files,
+        vec![directory.join("enc/base.lp"), directory.join("main.lp")]
+    );
+
+    let results = lint_files(&[directory.join("main.lp")], &Workspace::default()).unwrap();
+    let codes: Vec<Option<NumberOrString>> = results[0]
+        .1
+        .iter()
+        .map(|diagnostic| diagnostic.code.clone())
+        .collect();
+
+    // b is defined in the included file, only d is undefined
+    assert_eq!(codes, vec![Some(NumberOrString::Number(2001))]);
+    assert!(has_problems(&results));
+}
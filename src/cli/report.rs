@@ -0,0 +1,434 @@
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Url};
+
+use crate::diagnostics::diagnostic_codes::DiagnosticsCode;
+
+/**
+ * The version of the JSON output, it changes when fields are removed or change their meaning
+ */
+pub const JSON_REPORT_VERSION: u32 = 1;
+
+/**
+ * The base of the relative URIs in SARIF, the directory the linter runs in
+ */
+const SARIF_ROOT: &str = "%SRCROOT%";
+
+/**
+ * The formats the lint subcommand can print the diagnostics in
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    /**
+     * One line per diagnostic like clingo prints its messages
+     */
+    Text,
+    /**
+     * The stable JSON schema described in docs/cli.md
+     */
+    Json,
+    /**
+     * SARIF 2.1.0 for code scanning tools
+     */
+    Sarif,
+}
+
+impl OutputFormat {
+    pub fn from_name(name: &str) -> Option<OutputFormat> {
+        match name {
+            "text" => Some(OutputFormat::Text),
+            "json" => Some(OutputFormat::Json),
+            "sarif" => Some(OutputFormat::Sarif),
+            _ => None,
+        }
+    }
+
+    /**
+     * Format the diagnostics of all files in this format
+     */
+    pub fn format(self, results: &[(PathBuf, Vec<Diagnostic>)]) -> String {
+        match self {
+            OutputFormat::Text => format_text(results),
+            OutputFormat::Json => format_json(results),
+            OutputFormat::Sarif => format_sarif(results),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonReport {
+    version: u32,
+    diagnostics: Vec<JsonDiagnostic>,
+}
+
+#[derive(Serialize)]
+struct JsonDiagnostic {
+    file: String,
+    code: Option<i32>,
+    source: Option<String>,
+    severity: &'static str,
+    range: JsonRange,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct JsonRange {
+    start: JsonPosition,
+    end: JsonPosition,
+}
+
+/**
+ * A position where lines and columns start at 1
+ */
+#[derive(Serialize)]
+struct JsonPosition {
+    line: u32,
+    column: u32,
+}
+
+impl From<Position> for JsonPosition {
+    fn from(position: Position) -> Self {
+        JsonPosition {
+            line: position.line + 1,
+            column: position.character + 1,
+        }
+    }
+}
+
+/**
+ * The name clingo uses for the severity of a message
+ */
+fn get_severity_name(severity: Option<DiagnosticSeverity>) -> &'static str {
+    match severity {
+        Some(DiagnosticSeverity::WARNING) => "warning",
+        Some(DiagnosticSeverity::INFORMATION) => "info",
+        Some(DiagnosticSeverity::HINT) => "hint",
+        _ => "error",
+    }
+}
+
+fn get_code(diagnostic: &Diagnostic) -> Option<i32> {
+    match diagnostic.code {
+        Some(NumberOrString::Number(code)) => Some(code),
+        _ => None,
+    }
+}
+
+/**
+ * The path as it is shown in reports, always with forward slashes so reports of different systems can be compared
+ */
+fn get_report_path(file: &Path) -> String {
+    let path = file.to_string_lossy();
+    if cfg!(windows) {
+        path.replace('\\', "/")
+    } else {
+        path.to_string()
+    }
+}
+
+/**
+ * Format a diagnostic like clingo prints its messages, lines and columns start at 1
+ */
+pub fn format_diagnostic(file: &Path, diagnostic: &Diagnostic) -> String {
+    let code = match &diagnostic.code {
+        Some(NumberOrString::Number(code)) => format!(" [{}]", code),
+        Some(NumberOrString::String(code)) => format!(" [{}]", code),
+        None => String::new(),
+    };
+
+    format!(
+        "{}:{}:{}: {}: {}{}",
+        file.display(),
+        diagnostic.range.start.line + 1,
+        diagnostic.range.start.character + 1,
+        get_severity_name(diagnostic.severity),
+        diagnostic.message,
+        code
+    )
+}
+
+fn format_text(results: &[(PathBuf, Vec<Diagnostic>)]) -> String {
+    results
+        .iter()
+        .flat_map(|(file, diagnostics)| {
+            diagnostics
+                .iter()
+                .map(move |diagnostic| format_diagnostic(file, diagnostic) + "\n")
+        })
+        .collect()
+}
+
+fn format_json(results: &[(PathBuf, Vec<Diagnostic>)]) -> String {
+    let report = JsonReport {
+        version: JSON_REPORT_VERSION,
+        diagnostics: results
+            .iter()
+            .flat_map(|(file, diagnostics)| {
+                diagnostics.iter().map(move |diagnostic| JsonDiagnostic {
+                    file: get_report_path(file),
+                    code: get_code(diagnostic),
+                    source: diagnostic.source.clone(),
+                    severity: get_severity_name(diagnostic.severity),
+                    range: JsonRange {
+                        start: diagnostic.range.start.into(),
+                        end: diagnostic.range.end.into(),
+                    },
+                    message: diagnostic.message.clone(),
+                })
+            })
+            .collect(),
+    };
+
+    serde_json::to_string_pretty(&report).unwrap() + "\n"
+}
+
+/**
+ * The level of a result in SARIF, which has no levels for information and hints
+ */
+fn get_sarif_level(severity: Option<DiagnosticSeverity>) -> &'static str {
+    match severity {
+        Some(DiagnosticSeverity::WARNING) => "warning",
+        Some(DiagnosticSeverity::INFORMATION) | Some(DiagnosticSeverity::HINT) => "note",
+        _ => "error",
+    }
+}
+
+/**
+ * Percent-encode a relative path with forward slashes, so it can be used as a relative URI
+ */
+fn encode_relative_uri(path: &str) -> String {
+    let mut uri = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+
+    uri
+}
+
+/**
+ * The location of a file in SARIF. Relative paths are relative to the directory the linter runs in, which is the %SRCROOT% of the run
+ */
+fn get_artifact_location(file: &Path) -> Value {
+    if file.is_absolute() {
+        if let Ok(uri) = Url::from_file_path(file) {
+            return json!({ "uri": uri.as_str() });
+        }
+    }
+
+    json!({
+        "uri": encode_relative_uri(&get_report_path(file)),
+        "uriBaseId": SARIF_ROOT,
+    })
+}
+
+fn format_sarif(results: &[(PathBuf, Vec<Diagnostic>)]) -> String {
+    // Every code that occurs is a rule of the tool, the results refer to them by their index
+    let mut codes: Vec<i32> = results
+        .iter()
+        .flat_map(|(_, diagnostics)| diagnostics.iter().filter_map(get_code))
+        .collect();
+    codes.sort_unstable();
+    codes.dedup();
+
+    let rules: Vec<Value> = codes
+        .iter()
+        .map(|code| {
+            let mut rule = json!({ "id": code.to_string() });
+            if let Some(known) = DiagnosticsCode::from_i32(*code) {
+                rule["name"] = json!(known.get_name());
+            }
+            rule
+        })
+        .collect();
+
+    let sarif_results: Vec<Value> = results
+        .iter()
+        .flat_map(|(file, diagnostics)| {
+            let codes = &codes;
+            diagnostics.iter().map(move |diagnostic| {
+                let mut result = json!({
+                    "level": get_sarif_level(diagnostic.severity),
+                    "message": { "text": diagnostic.message },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": get_artifact_location(file),
+                            "region": {
+                                "startLine": diagnostic.range.start.line + 1,
+                                "startColumn": diagnostic.range.start.character + 1,
+                                "endLine": diagnostic.range.end.line + 1,
+                                "endColumn": diagnostic.range.end.character + 1,
+                            }
+                        }
+                    }],
+                });
+                if let Some(code) = get_code(diagnostic) {
+                    result["ruleId"] = json!(code.to_string());
+                    result["ruleIndex"] = json!(codes.binary_search(&code).unwrap());
+                }
+                if let Some(source) = &diagnostic.source {
+                    result["properties"] = json!({ "source": source });
+                }
+                result
+            })
+        })
+        .collect();
+
+    let mut run = json!({
+        "tool": {
+            "driver": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": rules,
+            }
+        },
+        "results": sarif_results,
+    });
+    if let Some(root) = env::current_dir()
+        .ok()
+        .and_then(|directory| Url::from_directory_path(directory).ok())
+    {
+        run["originalUriBaseIds"] = json!({ SARIF_ROOT: { "uri": root.as_str() } });
+    }
+
+    let sarif = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [run]
+    });
+
+    serde_json::to_string_pretty(&sarif).unwrap() + "\n"
+}
+
+#[cfg(test)]
+fn create_test_results() -> Vec<(PathBuf, Vec<Diagnostic>)> {
+    use tower_lsp::lsp_types::Range;
+
+    let create_diagnostic = |line, severity, code, source: &str, message: &str| Diagnostic {
+        range: Range::new(Position::new(line, 4), Position::new(line, 5)),
+        severity: Some(severity),
+        code: Some(NumberOrString::Number(code)),
+        source: Some(source.to_string()),
+        message: message.to_string(),
+        ..Diagnostic::default()
+    };
+
+    vec![
+        (
+            PathBuf::from("enc/main.lp"),
+            vec![
+                create_diagnostic(
+                    2,
+                    DiagnosticSeverity::WARNING,
+                    2004,
+                    "clinlint",
+                    "'Y' only occurs once",
+                ),
+                create_diagnostic(
+                    3,
+                    DiagnosticSeverity::ERROR,
+                    1001,
+                    "tree-sitter",
+                    "Expected a dot",
+                ),
+            ],
+        ),
+        (
+            PathBuf::from("base.lp"),
+            vec![create_diagnostic(
+                0,
+                DiagnosticSeverity::HINT,
+                2004,
+                "clinlint",
+                "'X' only occurs once",
+            )],
+        ),
+    ]
+}
+
+#[test]
+fn diagnostics_should_be_formatted_like_clingo() {
+    assert_eq!(
+        OutputFormat::Text.format(&create_test_results()),
+        "enc/main.lp:3:5: warning: 'Y' only occurs once [2004]\n\
+         enc/main.lp:4:5: error: Expected a dot [1001]\n\
+         base.lp:1:5: hint: 'X' only occurs once [2004]\n"
+    );
+}
+
+#[test]
+fn diagnostics_should_be_formatted_as_json() {
+    let report: Value =
+        serde_json::from_str(&OutputFormat::Json.format(&create_test_results())).unwrap();
+
+    assert_eq!(report["version"], JSON_REPORT_VERSION);
+    assert_eq!(report["diagnostics"].as_array().unwrap().len(), 3);
+    assert_eq!(
+        report["diagnostics"][1],
+        json!({
+            "file": "enc/main.lp",
+            "code": 1001,
+            "source": "tree-sitter",
+            "severity": "error",
+            "range": {
+                "start": { "line": 4, "column": 5 },
+                "end": { "line": 4, "column": 6 }
+            },
+            "message": "Expected a dot"
+        })
+    );
+}
+
+#[test]
+fn diagnostics_should_be_formatted_as_sarif() {
+    let sarif: Value =
+        serde_json::from_str(&OutputFormat::Sarif.format(&create_test_results())).unwrap();
+    let run = &sarif["runs"][0];
+
+    assert_eq!(sarif["version"], "2.1.0");
+    assert_eq!(
+        run["tool"]["driver"]["rules"],
+        json!([
+            { "id": "1001", "name": "expected-dot" },
+            { "id": "2004", "name": "singleton-variable" }
+        ])
+    );
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0]["ruleId"], "2004");
+    assert_eq!(results[0]["ruleIndex"], 1);
+    assert_eq!(results[0]["level"], "warning");
+    assert_eq!(results[0]["properties"]["source"], "clinlint");
+    assert_eq!(results[2]["level"], "note");
+    assert_eq!(
+        results[1]["locations"][0]["physicalLocation"],
+        json!({
+            "artifactLocation": { "uri": "enc/main.lp", "uriBaseId": "%SRCROOT%" },
+            "region": { "startLine": 4, "startColumn": 5, "endLine": 4, "endColumn": 6 }
+        })
+    );
+}
+
+#[test]
+fn sarif_locations_should_be_uris() {
+    assert_eq!(
+        get_artifact_location(Path::new("enc/my file.lp")),
+        json!({ "uri": "enc/my%20file.lp", "uriBaseId": "%SRCROOT%" })
+    );
+
+    let absolute = env::temp_dir().join("a#b.lp");
+    let location = get_artifact_location(&absolute);
+    let uri = location["uri"].as_str().unwrap();
+    assert!(uri.starts_with("file:///"));
+    assert!(uri.ends_with("/a%23b.lp"));
+    assert_eq!(location.get("uriBaseId"), None);
+}
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use tower_lsp::lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, NumberOrString, TextEdit,
+    WorkspaceEdit,
+};
+
+use crate::{diagnostics::diagnostic_codes::DiagnosticsCode, document::DocumentData};
+
+use self::{
+    singleton_variable_actions::check_singleton_variable_actions,
+    syntax_actions::{check_expected_dot_actions, check_missing_token_actions},
+    unsafe_variable_actions::check_unsafe_variable_actions,
+};
+
+pub mod singleton_variable_actions;
+pub mod syntax_actions;
+pub mod unsafe_variable_actions;
+
+/**
+ * Create the quick fixes for the diagnostics the client sent us
+ */
+pub fn check_code_actions(
+    document: &DocumentData,
+    diagnostics: &[Diagnostic],
+) -> Option<Vec<CodeActionOrCommand>> {
+    let mut actions = Vec::new();
+
+    for diagnostic in diagnostics {
+        let code = match diagnostic.code {
+            Some(NumberOrString::Number(code)) => code,
+            _ => continue,
+        };
+
+        if code == DiagnosticsCode::ExpectedDot.into_i32() {
+            actions.extend(check_expected_dot_actions(document, diagnostic));
+        } else if code == DiagnosticsCode::ExpectedMissingToken.into_i32() {
+            actions.extend(check_missing_token_actions(document, diagnostic));
+        } else if code == DiagnosticsCode::UnsafeVariable.into_i32() {
+            actions.extend(check_unsafe_variable_actions(document, diagnostic));
+        } else if code == DiagnosticsCode::SingletonVariable.into_i32() {
+            actions.extend(check_singleton_variable_actions(document, diagnostic));
+        }
+    }
+
+    Some(
+        actions
+            .into_iter()
+            .map(CodeActionOrCommand::CodeAction)
+            .collect(),
+    )
+}
+
+/**
+ * Create a quick fix that applies the edits to the document
+ */
+pub fn create_quick_fix(
+    document: &DocumentData,
+    title: String,
+    diagnostic: &Diagnostic,
+    edits: Vec<TextEdit>,
+    is_preferred: bool,
+) -> CodeAction {
+    let mut changes = HashMap::new();
+    changes.insert(document.uri.clone(), edits);
+
+    CodeAction {
+        title,
+        kind: Some(CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic.clone()]),
+        edit: Some(WorkspaceEdit::new(changes)),
+        command: None,
+        is_preferred: Some(is_preferred),
+        disabled: None,
+        data: None,
+    }
+}
@@ -0,0 +1,58 @@
+use tower_lsp::lsp_types::{CodeAction, Diagnostic, TextEdit};
+
+use crate::document::DocumentData;
+
+use super::create_quick_fix;
+
+/**
+ * A variable that only occurs once can be replaced by an anonymous variable
+ */
+pub fn check_singleton_variable_actions(
+    document: &DocumentData,
+    diagnostic: &Diagnostic,
+) -> Vec<CodeAction> {
+    let start = document.convert_position_to_point(diagnostic.range.start);
+    let end = document.convert_position_to_point(diagnostic.range.end);
+
+    let variable = match document
+        .tree
+        .root_node()
+        .descendant_for_point_range(start, end)
+    {
+        Some(node) if node.kind() == "VARIABLE" => node,
+        _ => return Vec::new(),
+    };
+
+    vec![create_quick_fix(
+        document,
+        format!(
+            "Replace '{}' with '_'",
+            document.get_source_for_range(variable.range())
+        ),
+        diagnostic,
+        vec![TextEdit::new(diagnostic.range, "_".to_string())],
+        true,
+    )]
+}
+
+#[cfg(test)]
+use dashmap::DashMap;
+
+#[cfg(test)]
+use crate::{diagnostics::run_diagnostics, test_utils::create_test_document};
+
+#[test]
+fn singleton_variables_should_be_replaced_by_anonymous_variable() {
+    let doc = create_test_document("a(X) :- b(X, Y).".to_string());
+    let diagnostics = run_diagnostics(doc.clone(), &DashMap::new(), 100);
+    let actions = check_singleton_variable_actions(&doc, &diagnostics[0]);
+
+    assert_eq!(actions[0].title, "Replace 'Y' with '_'");
+    assert_eq!(
+        actions[0].edit.as_ref().unwrap().changes.as_ref().unwrap()[&doc.uri][0]
+            .range
+            .start
+            .character,
+        13
+    );
+}
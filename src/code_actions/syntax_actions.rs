@@ -0,0 +1,100 @@
+use tower_lsp::lsp_types::{CodeAction, Diagnostic, Range, TextEdit};
+
+use crate::{diagnostics::tree_utils::humanize_token, document::DocumentData};
+
+use super::create_quick_fix;
+
+/**
+ * The tokens we can safely insert when tree-sitter reports them as missing
+ */
+const INSERTABLE_TOKENS: [&str; 3] = ["DOT", "RPAREN", "RBRACE"];
+
+/**
+ * The parser expected a dot after the error, insert it there
+ */
+pub fn check_expected_dot_actions(
+    document: &DocumentData,
+    diagnostic: &Diagnostic,
+) -> Vec<CodeAction> {
+    let position = diagnostic.range.end;
+
+    vec![create_quick_fix(
+        document,
+        "Insert missing '.'".to_string(),
+        diagnostic,
+        vec![TextEdit::new(
+            Range::new(position, position),
+            ".".to_string(),
+        )],
+        true,
+    )]
+}
+
+/**
+ * Tree-sitter inserted a missing token to recover from the error, insert the same token in the document
+ */
+pub fn check_missing_token_actions(
+    document: &DocumentData,
+    diagnostic: &Diagnostic,
+) -> Vec<CodeAction> {
+    document
+        .semantics
+        .syntax
+        .get_missing()
+        .iter()
+        .filter(|missing| {
+            document.convert_range(missing.range) == diagnostic.range
+                && INSERTABLE_TOKENS.contains(&missing.missing.as_str())
+        })
+        .map(|missing| {
+            let token = humanize_token(&missing.missing);
+
+            create_quick_fix(
+                document,
+                format!("Insert missing '{}'", token),
+                diagnostic,
+                vec![TextEdit::new(
+                    Range::new(diagnostic.range.start, diagnostic.range.start),
+                    token.to_string(),
+                )],
+                true,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+use dashmap::DashMap;
+
+#[cfg(test)]
+use crate::{diagnostics::run_diagnostics, test_utils::create_test_document};
+
+#[cfg(test)]
+fn apply_first_fix(source: &str) -> String {
+    let doc = create_test_document(source.to_string());
+    let diagnostics = run_diagnostics(doc.clone(), &DashMap::new(), 100);
+
+    let action = match &super::check_code_actions(&doc, &diagnostics).unwrap()[0] {
+        tower_lsp::lsp_types::CodeActionOrCommand::CodeAction(action) => action.clone(),
+        _ => panic!("Expected a code action"),
+    };
+    let edit = &action.edit.unwrap().changes.unwrap()[&doc.uri][0];
+
+    let mut result = source.to_string();
+    result.insert_str(edit.range.start.character as usize, &edit.new_text);
+    result
+}
+
+#[test]
+fn expected_dot_should_insert_dot_after_error() {
+    assert_eq!(apply_first_fix("a. d c :- a."), "a. d. c :- a.");
+}
+
+#[test]
+fn missing_tokens_should_be_inserted() {
+    assert_eq!(apply_first_fix("a. b(c. c."), "a. b(c). c.");
+    assert_eq!(
+        apply_first_fix("a(N) :- N = #count{X : count(X)."),
+        "a(N) :- N = #count{X : count(X)}."
+    );
+}
@@ -0,0 +1,383 @@
+use tower_lsp::lsp_types::{CodeAction, Diagnostic, Range, TextEdit};
+use tree_sitter::Node;
+
+use crate::{
+    diagnostics::{
+        statement_analysis::get_variables_in_statement,
+        tree_utils::{get_ancestor, get_child, get_descendants, is_below},
+    },
+    document::DocumentData,
+};
+
+use super::create_quick_fix;
+
+/**
+ * Create the fixes for an unsafe variable: replace it with an anonymous variable, add a domain literal or remove the negation that blocks it
+ */
+pub fn check_unsafe_variable_actions(
+    document: &DocumentData,
+    diagnostic: &Diagnostic,
+) -> Vec<CodeAction> {
+    let start = document.convert_position_to_point(diagnostic.range.start);
+    let end = document.convert_position_to_point(diagnostic.range.end);
+
+    let variable = match document
+        .tree
+        .root_node()
+        .descendant_for_point_range(start, end)
+    {
+        Some(node) if node.kind() == "VARIABLE" => node,
+        _ => return Vec::new(),
+    };
+    let statement = match get_ancestor(variable, "statement") {
+        Some(statement) => statement,
+        None => return Vec::new(),
+    };
+
+    let mut actions = Vec::new();
+    actions.extend(check_anonymous_variable_action(
+        document, diagnostic, variable, statement,
+    ));
+    actions.extend(check_domain_literal_actions(
+        document, diagnostic, variable, statement,
+    ));
+    actions.extend(check_remove_negation_action(
+        document, diagnostic, variable, statement,
+    ));
+
+    actions
+}
+
+/**
+ * A variable that occurs only once in the body is not needed, an anonymous variable does the same
+ */
+fn check_anonymous_variable_action(
+    document: &DocumentData,
+    diagnostic: &Diagnostic,
+    variable: Node,
+    statement: Node,
+) -> Option<CodeAction> {
+    let name = document.get_source_for_range(variable.range());
+    let source = document.get_bytes();
+
+    let occurences = get_variables_in_statement(&statement, &source)
+        .iter()
+        .filter(|(_, other, _)| *other == name)
+        .count();
+
+    // An anonymous variable in the head or outside of an atom is still unsafe
+    if occurences != 1
+        || is_below(variable, statement, "head")
+        || !is_below(variable, statement, "atom")
+    {
+        return None;
+    }
+
+    Some(create_quick_fix(
+        document,
+        format!("Replace '{}' with '_'", name),
+        diagnostic,
+        vec![TextEdit::new(diagnostic.range, "_".to_string())],
+        false,
+    ))
+}
+
+/**
+ * Look at other statements where the predicates containing this variable are used, if a positive body literal provides the variable there we suggest that literal as domain
+ */
+fn check_domain_literal_actions(
+    document: &DocumentData,
+    diagnostic: &Diagnostic,
+    variable: Node,
+    statement: Node,
+) -> Vec<CodeAction> {
+    let name = document.get_source_for_range(variable.range());
+    let statement_semantics = document
+        .semantics
+        .get_statement_semantics_for_node(statement.id());
+
+    // Only global variables of rules can be provided by a new body literal
+    if !statement_semantics.global_vars.contains(&name)
+        || !matches!(
+            statement.child(0).map(|child| child.kind()),
+            Some("head") | Some("IF")
+        )
+    {
+        return Vec::new();
+    }
+
+    // The argument positions the variable occurs at in this statement
+    let mut positions: Vec<(String, usize, usize)> = Vec::new();
+    for atom in get_descendants(statement, "atom") {
+        for (index, argument) in get_atom_arguments(atom).iter().enumerate() {
+            if get_plain_variable(document, *argument) == Some(name.clone()) {
+                positions.push(get_atom_signature(document, atom, index));
+            }
+        }
+    }
+
+    let mut domains: Vec<(String, usize, usize)> = Vec::new();
+    let root = document.tree.root_node();
+    for other in root.children(&mut root.walk()) {
+        if other.kind() != "statement" || other.id() == statement.id() {
+            continue;
+        }
+
+        let atoms = get_descendants(other, "atom");
+        for atom in &atoms {
+            for (index, argument) in get_atom_arguments(*atom).iter().enumerate() {
+                if !positions.contains(&get_atom_signature(document, *atom, index)) {
+                    continue;
+                }
+                let other_variable = match get_plain_variable(document, *argument) {
+                    Some(other_variable) => other_variable,
+                    None => continue,
+                };
+
+                // Find the positive body literals that provide the same variable
+                for domain in &atoms {
+                    if domain.id() == atom.id() || !is_positive_body_atom(*domain, other) {
+                        continue;
+                    }
+
+                    for (domain_index, domain_argument) in
+                        get_atom_arguments(*domain).iter().enumerate()
+                    {
+                        let signature = get_atom_signature(document, *domain, domain_index);
+                        if get_plain_variable(document, *domain_argument).as_ref()
+                            == Some(&other_variable)
+                            && !domains.contains(&signature)
+                        {
+                            domains.push(signature);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    domains
+        .iter()
+        .filter_map(|(identifier, arity, index)| {
+            let arguments: Vec<String> = (0..*arity)
+                .map(|position| {
+                    if position == *index {
+                        name.clone()
+                    } else {
+                        "_".to_string()
+                    }
+                })
+                .collect();
+            let literal = format!("{}({})", identifier, arguments.join(", "));
+
+            let edit = match get_child(statement, "bodydot") {
+                Some(body) => {
+                    let position = document.convert_point_to_position(body.start_position());
+                    TextEdit::new(Range::new(position, position), format!("{}, ", literal))
+                }
+                None => {
+                    let dot = get_child(statement, "DOT")?;
+                    let position = document.convert_point_to_position(dot.start_position());
+                    TextEdit::new(Range::new(position, position), format!(" :- {}", literal))
+                }
+            };
+
+            Some(create_quick_fix(
+                document,
+                format!("Add domain literal '{}'", literal),
+                diagnostic,
+                vec![edit],
+                false,
+            ))
+        })
+        .collect()
+}
+
+/**
+ * A negated literal never provides a variable, removing the negation makes it provide the variable
+ */
+fn check_remove_negation_action(
+    document: &DocumentData,
+    diagnostic: &Diagnostic,
+    variable: Node,
+    statement: Node,
+) -> Option<CodeAction> {
+    if !is_below(variable, statement, "bodydot") {
+        return None;
+    }
+
+    let literal = get_ancestor(variable, "literal")?;
+    let negation = literal.child(0)?;
+    if negation.kind() != "NOT" {
+        return None;
+    }
+
+    // Remove every not in front of the atom
+    let mut atom = negation;
+    while atom.kind() == "NOT" {
+        atom = atom.next_sibling()?;
+    }
+
+    Some(create_quick_fix(
+        document,
+        format!(
+            "Remove negation from '{}'",
+            document.get_source_for_range(atom.range())
+        ),
+        diagnostic,
+        vec![TextEdit::new(
+            Range::new(
+                document.convert_point_to_position(negation.start_position()),
+                document.convert_point_to_position(atom.start_position()),
+            ),
+            "".to_string(),
+        )],
+        false,
+    ))
+}
+
+/**
+ * Get the identifier, the arity and an argument position of an atom
+ */
+fn get_atom_signature(document: &DocumentData, atom: Node, index: usize) -> (String, usize, usize) {
+    let identifier = atom
+        .children(&mut atom.walk())
+        .find(|child| child.kind() == "identifier")
+        .map(|identifier| document.get_source_for_range(identifier.range()))
+        .unwrap_or_default();
+
+    (identifier, get_atom_arguments(atom).len(), index)
+}
+
+/**
+ * Get the arguments of an atom in order, atoms with pools are skipped
+ */
+fn get_atom_arguments(atom: Node) -> Vec<Node> {
+    let mut arguments = Vec::new();
+
+    let termvec = match get_child(atom, "argvec") {
+        Some(argvec) if argvec.child_count() == 1 => match argvec.child(0) {
+            Some(termvec) if termvec.kind() == "termvec" => termvec,
+            _ => return arguments,
+        },
+        _ => return arguments,
+    };
+
+    let mut stack = vec![termvec];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "termvec" => {
+                let children: Vec<Node> = node.children(&mut node.walk()).collect();
+                stack.extend(children.into_iter().rev());
+            }
+            "term" => arguments.push(node),
+            _ => {}
+        }
+    }
+
+    arguments
+}
+
+/**
+ * If the term only consists of a variable return its name
+ */
+fn get_plain_variable(document: &DocumentData, term: Node) -> Option<String> {
+    let child = term.child(0)?;
+    if term.child_count() != 1 || child.kind() != "VARIABLE" {
+        return None;
+    }
+
+    Some(document.get_source_for_range(child.range()))
+}
+
+/**
+ * Check if an atom is a literal directly in the body of the statement without negation
+ */
+fn is_positive_body_atom(atom: Node, statement: Node) -> bool {
+    let literal = match atom.parent() {
+        Some(literal) if literal.kind() == "literal" => literal,
+        _ => return false,
+    };
+    if literal.child(0).map(|child| child.kind()) == Some("NOT") {
+        return false;
+    }
+
+    let mut parent = literal.parent();
+    while let Some(current) = parent {
+        match current.kind() {
+            "bodycomma" => parent = current.parent(),
+            "bodydot" => return current.parent() == Some(statement),
+            _ => return false,
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+use dashmap::DashMap;
+
+#[cfg(test)]
+use crate::{diagnostics::run_diagnostics, test_utils::create_test_document};
+
+#[cfg(test)]
+fn get_fix_titles(source: &str) -> Vec<String> {
+    let doc = create_test_document(source.to_string());
+    let diagnostics = run_diagnostics(doc.clone(), &DashMap::new(), 100);
+
+    check_unsafe_variable_actions(&doc, &diagnostics[0])
+        .into_iter()
+        .map(|action| action.title)
+        .collect()
+}
+
+#[test]
+fn single_occurence_in_negated_literal_should_be_replaced_by_anonymous_variable() {
+    let titles = get_fix_titles("a :- not b(X).");
+
+    assert!(titles.contains(&"Replace 'X' with '_'".to_string()));
+    assert!(titles.contains(&"Remove negation from 'b(X)'".to_string()));
+}
+
+#[test]
+fn variables_in_the_head_should_not_be_replaced() {
+    let titles = get_fix_titles("a(X) :- b.");
+
+    assert!(!titles.contains(&"Replace 'X' with '_'".to_string()));
+}
+
+#[test]
+fn domain_literals_should_be_found_in_other_rules() {
+    let titles =
+        get_fix_titles("node(1). reach(X) :- node(X), not blocked(X). safe(X) :- not blocked(X).");
+
+    assert_eq!(
+        titles
+            .iter()
+            .filter(|title| title.starts_with("Add domain literal"))
+            .collect::<Vec<&String>>(),
+        vec!["Add domain literal 'node(X)'"]
+    );
+}
+
+#[test]
+fn domain_literals_should_be_inserted_in_the_body() {
+    let get_edit = |source: &str| {
+        let doc = create_test_document(source.to_string());
+        let diagnostics = run_diagnostics(doc.clone(), &DashMap::new(), 100);
+        let action = check_unsafe_variable_actions(&doc, &diagnostics[0])
+            .into_iter()
+            .find(|action| action.title == "Add domain literal 'arc(X, _)'")
+            .unwrap();
+
+        action.edit.unwrap().changes.unwrap()[&doc.uri][0].clone()
+    };
+
+    let edit = get_edit("path(X) :- arc(X, Y). path(X) :- not b.");
+    assert_eq!(edit.new_text, "arc(X, _), ");
+    assert_eq!(edit.range.start.character, 33);
+
+    let edit = get_edit("path(X) :- arc(X, Y). path(X).");
+    assert_eq!(edit.new_text, " :- arc(X, _)");
+    assert_eq!(edit.range.start.character, 29);
+}
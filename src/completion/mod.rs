@@ -23,17 +23,17 @@ pub fn check_completion(
 ) -> Option<Vec<CompletionItem>> {
     //Client requested completion
 
-    let node: Option<Node> = if position.character > 0 {
-        document.tree.root_node().descendant_for_point_range(
-            Point {
-                row: position.line as usize,
-                column: (position.character - 1) as usize,
-            },
-            Point {
-                row: position.line as usize,
-                column: (position.character - 1) as usize,
-            },
-        )
+    // The node of the character before the cursor, the columns of tree-sitter count bytes so this is the last byte of that character
+    let point = document.convert_position_to_point(position);
+    let node: Option<Node> = if point.column > 0 {
+        let before = Point {
+            row: point.row,
+            column: point.column - 1,
+        };
+        document
+            .tree
+            .root_node()
+            .descendant_for_point_range(before, before)
     } else {
         None
     };
@@ -12,8 +12,8 @@ pub fn predicate_completion_resolver(
 ) -> Option<Vec<CompletionItem>> {
     let mut items = Vec::new();
 
-    if node.is_some() {
-        let mut parent = node.unwrap().parent();
+    if let Some(node) = node {
+        let mut parent = node.parent();
         while parent.is_some() {
             if parent.unwrap().kind() == "statement" {
                 //Find all variables used in this statement and return this to the user
@@ -38,9 +38,7 @@ pub fn predicate_completion_resolver(
             let mut insert_text_snippet = identifier.clone();
 
             // Check if this predicate isn't at the current location of the cursor, if it is we do not suggest this predicate
-            if occurences.len() == 1
-                && document.get_source_for_range(node.unwrap().range()) == identifier
-            {
+            if occurences.len() == 1 && document.get_source_for_range(node.range()) == identifier {
                 continue;
             }
 
@@ -49,7 +47,7 @@ pub fn predicate_completion_resolver(
 
                 for i in 1..arity + 1 {
                     insert_text_snippet += &format!("${{{:?}:()}}", i).to_string();
-                    if arity >= i + 1 {
+                    if arity > i {
                         insert_text_snippet += ", ";
                     }
                 }
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::{
+    document::{DocumentData, PositionEncoding},
+    semantics::encoding_semantic::DEFAULT_INTERVAL_LIMIT,
+};
+
+/**
+ * The settings of the language server that the client can change
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServerConfiguration {
+    /**
+     * Constants given on the command line with -c, these replace the values of #const statements
+     */
+    pub constants: HashMap<String, String>,
+    pub interval_limit: usize,
+    /**
+     * The position encoding the client agreed on while initializing, it is not part of the settings
+     */
+    pub position_encoding: PositionEncoding,
+}
+
+impl ServerConfiguration {
+    pub const SECTION: &'static str = "asp-language-server";
+
+    /**
+     * Create the configuration from the settings the client sent with workspace/didChangeConfiguration.
+     * The settings can be nested in the "asp-language-server" section, the constants are a list of arguments like "-c n=5" or "n=5"
+     */
+    pub fn from_settings(settings: &Value) -> ServerConfiguration {
+        let settings = settings
+            .get(ServerConfiguration::SECTION)
+            .unwrap_or(settings);
+        let mut configuration = ServerConfiguration::default();
+
+        if let Some(constants) = settings.get("constants").and_then(|value| value.as_array()) {
+            for argument in constants.iter().filter_map(|value| value.as_str()) {
+                if let Some((name, value)) = ServerConfiguration::parse_constant_argument(argument)
+                {
+                    configuration.constants.insert(name, value);
+                }
+            }
+        }
+
+        if let Some(limit) = settings
+            .get("intervalLimit")
+            .and_then(|value| value.as_u64())
+        {
+            configuration.interval_limit = limit as usize;
+        }
+
+        configuration
+    }
+
+    /**
+     * Parse a constant argument like "-c n=5", "--const n=5" or "n=5" into the name and the value of the constant
+     */
+    pub fn parse_constant_argument(argument: &str) -> Option<(String, String)> {
+        let argument = argument.trim();
+        let argument = ["--const=", "--const ", "-c"]
+            .iter()
+            .find_map(|prefix| argument.strip_prefix(prefix))
+            .unwrap_or(argument);
+
+        let (name, value) = argument.split_once('=')?;
+        let (name, value) = (name.trim(), value.trim());
+        if name.is_empty() || value.is_empty() {
+            return None;
+        }
+
+        Some((name.to_string(), value.to_string()))
+    }
+
+    /**
+     * Use this configuration for the semantic analysis of the document, the semantics have to be generated again afterwards
+     */
+    pub fn apply(&self, document: &mut DocumentData) {
+        document.semantics.constant_overrides = self.constants.clone();
+        document.semantics.interval_limit = self.interval_limit;
+        document.position_encoding = self.position_encoding;
+    }
+}
+
+impl Default for ServerConfiguration {
+    fn default() -> Self {
+        ServerConfiguration {
+            constants: HashMap::new(),
+            interval_limit: DEFAULT_INTERVAL_LIMIT,
+            position_encoding: PositionEncoding::default(),
+        }
+    }
+}
+
+#[test]
+fn constant_arguments_should_be_parsed() {
+    assert_eq!(
+        ServerConfiguration::parse_constant_argument("-c n=5"),
+        Some(("n".to_string(), "5".to_string()))
+    );
+    assert_eq!(
+        ServerConfiguration::parse_constant_argument("--const=max = 10"),
+        Some(("max".to_string(), "10".to_string()))
+    );
+    assert_eq!(
+        ServerConfiguration::parse_constant_argument("-cn=-2"),
+        Some(("n".to_string(), "-2".to_string()))
+    );
+    assert_eq!(ServerConfiguration::parse_constant_argument("-c n"), None);
+}
+
+#[test]
+fn settings_should_be_read_from_the_section() {
+    let configuration = ServerConfiguration::from_settings(&serde_json::json!({
+        "asp-language-server": {
+            "constants": ["-c n=5", "m=2", "invalid"],
+            "intervalLimit": 50
+        }
+    }));
+
+    assert_eq!(configuration.constants.len(), 2);
+    assert_eq!(configuration.constants["n"], "5");
+    assert_eq!(configuration.interval_limit, 50);
+
+    // Clients that do not use sections send the settings directly
+    let configuration =
+        ServerConfiguration::from_settings(&serde_json::json!({ "constants": ["n=1"] }));
+    assert_eq!(configuration.constants["n"], "1");
+}
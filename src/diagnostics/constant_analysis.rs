@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+
+use dashmap::DashMap;
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+use crate::{
+    document::DocumentData, semantics::constant_semantics::ConstantOccurenceSemantics,
+    workspace::Program,
+};
+
+#[cfg(test)]
+use crate::test_utils::create_test_document;
+
+use super::{diagnostic_codes::DiagnosticsCode, diagnostic_run_data::DiagnosticsRunData};
+
+/**
+ * Analyze how the constants declared with #const are declared and used, a constant can be used in any document of the program
+ */
+pub fn constant_analysis(
+    diagnostic_data: &mut DiagnosticsRunData,
+    document: &DocumentData,
+    documents: &DashMap<String, DocumentData>,
+    program: &Program,
+) {
+    let mut duplicates: Vec<(String, ConstantOccurenceSemantics)> = Vec::new();
+    let mut declared: Vec<(String, ConstantOccurenceSemantics)> = Vec::new();
+    let mut cyclic: Vec<(String, ConstantOccurenceSemantics)> = Vec::new();
+
+    for constant in document.semantics.constant_semantics.constants.iter() {
+        // Iterating the occurences directly avoids locking the map again
+        let mut declarations: Vec<ConstantOccurenceSemantics> = constant
+            .value()
+            .iter()
+            .filter(|occurence| occurence.is_declaration)
+            .cloned()
+            .collect();
+        declarations.sort_by_key(|declaration| declaration.range.start_byte);
+
+        // The first declaration is the one clingo uses
+        if document
+            .semantics
+            .constant_semantics
+            .cyclic
+            .contains(constant.key())
+        {
+            cyclic.push((constant.key().clone(), declarations[0].clone()));
+        }
+        for declaration in declarations.iter().skip(1) {
+            duplicates.push((constant.key().clone(), declaration.clone()));
+        }
+
+        for declaration in declarations {
+            declared.push((constant.key().clone(), declaration));
+        }
+    }
+
+    let mut used: HashSet<String> = HashSet::new();
+    program.for_each_document(document, documents, |other| {
+        for constant in other.semantics.constant_semantics.constants.iter() {
+            if constant
+                .value()
+                .iter()
+                .any(|occurence| !occurence.is_declaration)
+            {
+                used.insert(constant.key().clone());
+            }
+        }
+    });
+    let mut unused: Vec<(String, ConstantOccurenceSemantics)> = declared
+        .into_iter()
+        .filter(|(identifier, _)| !used.contains(identifier))
+        .collect();
+
+    // Report in the order they occur in the document
+    cyclic.sort_by_key(|(_, occurence)| occurence.range.start_byte);
+    duplicates.sort_by_key(|(_, occurence)| occurence.range.start_byte);
+    unused.sort_by_key(|(_, occurence)| occurence.range.start_byte);
+
+    for (identifier, occurence) in cyclic {
+        if diagnostic_data.current_number_of_problems >= diagnostic_data.maximum_number_of_problems
+        {
+            return;
+        }
+
+        diagnostic_data.create_linter_diagnostic(
+            occurence.range,
+            DiagnosticSeverity::WARNING,
+            DiagnosticsCode::ConstantCycle.into_i32(),
+            format!(
+                "'{}' is defined in terms of itself, the constant has no value",
+                identifier
+            ),
+        );
+    }
+
+    for (identifier, occurence) in duplicates {
+        if diagnostic_data.current_number_of_problems >= diagnostic_data.maximum_number_of_problems
+        {
+            return;
+        }
+
+        diagnostic_data.create_linter_diagnostic(
+            occurence.range,
+            DiagnosticSeverity::WARNING,
+            DiagnosticsCode::DuplicateConstant.into_i32(),
+            format!("'{}' is already declared with #const", identifier),
+        );
+    }
+
+    for (identifier, occurence) in unused {
+        if diagnostic_data.current_number_of_problems >= diagnostic_data.maximum_number_of_problems
+        {
+            return;
+        }
+
+        diagnostic_data.create_unnecessary_linter_diagnostic(
+            occurence.range,
+            DiagnosticsCode::UnusedConstant.into_i32(),
+            format!("'{}' is declared, but never used", identifier),
+        );
+    }
+}
+
+#[cfg(test)]
+fn get_constant_messages(source: &str) -> Vec<String> {
+    let mut diags = DiagnosticsRunData::create_test_diagnostics();
+
+    constant_analysis(
+        &mut diags,
+        &create_test_document(source.to_string()),
+        &DashMap::new(),
+        &Program::default(),
+    );
+
+    diags
+        .total_diagnostics
+        .iter()
+        .map(|diagnostic| {
+            format!(
+                "{}: {}",
+                diagnostic.range.start.character, diagnostic.message
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn constants_declared_twice_should_be_detected() {
+    assert_eq!(
+        get_constant_messages("#const n = 1. #const n = 2. a(n)."),
+        vec!["21: 'n' is already declared with #const"]
+    );
+}
+
+#[test]
+fn constants_that_are_never_used_should_be_unnecessary() {
+    assert_eq!(
+        get_constant_messages("#const n = 1. #const m = n + 1. a(1)."),
+        vec!["21: 'm' is declared, but never used"]
+    );
+    assert!(get_constant_messages("a(1..n). #const n = 1.").is_empty());
+}
+
+#[test]
+fn constants_defined_in_terms_of_themselves_should_be_detected() {
+    assert_eq!(
+        get_constant_messages("#const a = b. #const b = a+1. #const c = c. #const d = a. e(d)."),
+        vec![
+            "7: 'a' is defined in terms of itself, the constant has no value",
+            "21: 'b' is defined in terms of itself, the constant has no value",
+            "37: 'c' is defined in terms of itself, the constant has no value"
+        ]
+    );
+    assert!(get_constant_messages("#const a = b+1. #const b = 2. c(a).").is_empty());
+}
@@ -1,6 +1,7 @@
 /**
  * DIAGNOSTICS CODES TREE-SITTER
  */
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum DiagnosticsCode {
     /**
      * ERROR CODES TREE SITTER
@@ -15,10 +16,101 @@ pub enum DiagnosticsCode {
      * ERROR CODES ANALYSIS
      */
     UnsafeVariable = 2000,
+    UndefinedPredicate = 2001,
+    UnusedPredicate = 2002,
+    ArityMismatch = 2003,
+    SingletonVariable = 2004,
+    DivisionByZero = 2005,
+    DuplicateConstant = 2006,
+    UnusedConstant = 2007,
+    MissingInclude = 2008,
+    IncludeCycle = 2009,
+    ArithmeticOverflow = 2010,
+    ConstantCycle = 2011,
 }
 
 impl DiagnosticsCode {
+    const ALL: [DiagnosticsCode; 15] = [
+        DiagnosticsCode::UnknownParseState,
+        DiagnosticsCode::ExpectedDot,
+        DiagnosticsCode::ExpectedMissingToken,
+        DiagnosticsCode::UnsafeVariable,
+        DiagnosticsCode::UndefinedPredicate,
+        DiagnosticsCode::UnusedPredicate,
+        DiagnosticsCode::ArityMismatch,
+        DiagnosticsCode::SingletonVariable,
+        DiagnosticsCode::DivisionByZero,
+        DiagnosticsCode::DuplicateConstant,
+        DiagnosticsCode::UnusedConstant,
+        DiagnosticsCode::MissingInclude,
+        DiagnosticsCode::IncludeCycle,
+        DiagnosticsCode::ArithmeticOverflow,
+        DiagnosticsCode::ConstantCycle,
+    ];
+
     pub fn into_i32(self) -> i32 {
         self as i32
     }
+
+    pub fn from_i32(code: i32) -> Option<DiagnosticsCode> {
+        DiagnosticsCode::ALL
+            .into_iter()
+            .find(|other| other.into_i32() == code)
+    }
+
+    /**
+     * The name of the code like "unused-predicate", the names of the lints are used in asp-project.toml
+     */
+    pub fn get_name(self) -> &'static str {
+        match self {
+            DiagnosticsCode::UnknownParseState => "unknown-parse-state",
+            DiagnosticsCode::ExpectedDot => "expected-dot",
+            DiagnosticsCode::ExpectedMissingToken => "expected-missing-token",
+            DiagnosticsCode::UnsafeVariable => "unsafe-variable",
+            DiagnosticsCode::UndefinedPredicate => "undefined-predicate",
+            DiagnosticsCode::UnusedPredicate => "unused-predicate",
+            DiagnosticsCode::ArityMismatch => "arity-mismatch",
+            DiagnosticsCode::SingletonVariable => "singleton-variable",
+            DiagnosticsCode::DivisionByZero => "division-by-zero",
+            DiagnosticsCode::DuplicateConstant => "duplicate-constant",
+            DiagnosticsCode::UnusedConstant => "unused-constant",
+            DiagnosticsCode::MissingInclude => "missing-include",
+            DiagnosticsCode::IncludeCycle => "include-cycle",
+            DiagnosticsCode::ArithmeticOverflow => "arithmetic-overflow",
+            DiagnosticsCode::ConstantCycle => "constant-cycle",
+        }
+    }
+
+    /**
+     * If the code belongs to a lint of clinlint, the other codes are errors of the parser
+     */
+    pub fn is_lint(self) -> bool {
+        self.into_i32() >= 2000
+    }
+
+    /**
+     * Find a lint by its name like "unused-predicate" or by its code like "2002"
+     */
+    pub fn from_lint_name(name: &str) -> Option<DiagnosticsCode> {
+        DiagnosticsCode::ALL.into_iter().find(|code| {
+            code.is_lint() && (code.get_name() == name || code.into_i32().to_string() == name)
+        })
+    }
+}
+
+#[test]
+fn lints_should_be_found_by_name_or_code() {
+    assert_eq!(
+        DiagnosticsCode::from_lint_name("unused-predicate"),
+        Some(DiagnosticsCode::UnusedPredicate)
+    );
+    assert_eq!(
+        DiagnosticsCode::from_lint_name("2004"),
+        Some(DiagnosticsCode::SingletonVariable)
+    );
+    assert_eq!(DiagnosticsCode::from_lint_name("expected-dot"), None);
+    assert_eq!(
+        DiagnosticsCode::from_i32(1001),
+        Some(DiagnosticsCode::ExpectedDot)
+    );
 }
@@ -1,4 +1,7 @@
-use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, DiagnosticTag, Position, Range};
+use tree_sitter::Point;
+
+use crate::document::DocumentData;
 
 /**
  * A object that contains all the diagnostic data which was found
@@ -8,11 +11,34 @@ pub struct DiagnosticsRunData {
     pub maximum_number_of_problems: u32,
     pub current_number_of_problems: u32,
 
-    //A list of diagnostics to be send to the user
+    //A list of diagnostics to be send to the user, the characters of their ranges count bytes like tree-sitter does
     pub total_diagnostics: Vec<Diagnostic>,
 }
 
 impl DiagnosticsRunData {
+    /**
+     * Get the diagnostics with their ranges in the position encoding of the document
+     */
+    pub fn into_diagnostics(self, document: &DocumentData) -> Vec<Diagnostic> {
+        let convert = |position: Position| {
+            document.convert_point_to_position(Point {
+                row: position.line as usize,
+                column: position.character as usize,
+            })
+        };
+
+        self.total_diagnostics
+            .into_iter()
+            .map(|mut diagnostic| {
+                diagnostic.range = Range::new(
+                    convert(diagnostic.range.start),
+                    convert(diagnostic.range.end),
+                );
+                diagnostic
+            })
+            .collect()
+    }
+
     /**
      * Create a diagnostic message from clinlint
      */
@@ -29,6 +55,26 @@ impl DiagnosticsRunData {
             code_number,
             "clinlint".to_string(),
             message,
+            None,
+        )
+    }
+
+    /**
+     * Create a hint from clinlint for code that has no effect, clients usually render this faded out
+     */
+    pub fn create_unnecessary_linter_diagnostic(
+        &mut self,
+        range: tree_sitter::Range,
+        code_number: i32,
+        message: String,
+    ) {
+        self.create_diagnostic(
+            range,
+            DiagnosticSeverity::HINT,
+            code_number,
+            "clinlint".to_string(),
+            message,
+            Some(vec![DiagnosticTag::UNNECESSARY]),
         )
     }
 
@@ -48,6 +94,7 @@ impl DiagnosticsRunData {
             code_number,
             "tree-sitter".to_string(),
             message,
+            None,
         )
     }
 
@@ -61,24 +108,27 @@ impl DiagnosticsRunData {
         code_number: i32,
         source: String,
         message: String,
+        tags: Option<Vec<DiagnosticTag>>,
     ) {
-        self.total_diagnostics
-            .push(Diagnostic::new_with_code_number(
-                Range::new(
-                    Position::new(
-                        range.start_point.row.try_into().unwrap(),
-                        range.start_point.column.try_into().unwrap(),
-                    ),
-                    Position::new(
-                        range.end_point.row.try_into().unwrap(),
-                        range.end_point.column.try_into().unwrap(),
-                    ),
+        let mut diagnostic = Diagnostic::new_with_code_number(
+            Range::new(
+                Position::new(
+                    range.start_point.row.try_into().unwrap(),
+                    range.start_point.column.try_into().unwrap(),
+                ),
+                Position::new(
+                    range.end_point.row.try_into().unwrap(),
+                    range.end_point.column.try_into().unwrap(),
                 ),
-                severity,
-                code_number,
-                Some(source),
-                message,
-            ));
+            ),
+            severity,
+            code_number,
+            Some(source),
+            message,
+        );
+        diagnostic.tags = tags;
+
+        self.total_diagnostics.push(diagnostic);
         self.current_number_of_problems += 1;
     }
 
@@ -91,3 +141,19 @@ impl DiagnosticsRunData {
         }
     }
 }
+
+#[test]
+fn diagnostic_ranges_should_be_converted_to_the_position_encoding() {
+    let doc = crate::test_utils::create_test_document("a(\"ä😀\") :- b(X).".to_string());
+    let diagnostics = crate::diagnostics::run_diagnostics(doc, &dashmap::DashMap::new(), 100);
+
+    // The singleton X starts at byte 17, but at UTF-16 code unit 14
+    let singleton = diagnostics
+        .iter()
+        .find(|diagnostic| diagnostic.message.starts_with("'X'"))
+        .unwrap();
+    assert_eq!(
+        singleton.range,
+        Range::new(Position::new(0, 14), Position::new(0, 15))
+    );
+}
@@ -0,0 +1,140 @@
+use std::collections::HashSet;
+
+use dashmap::DashMap;
+use tower_lsp::lsp_types::{DiagnosticSeverity, Url};
+
+use crate::{
+    document::DocumentData,
+    workspace::{get_includes, resolve_include},
+};
+
+use super::{diagnostic_codes::DiagnosticsCode, diagnostic_run_data::DiagnosticsRunData};
+
+/**
+ * Check that the files included with #include exist and do not include the document again
+ */
+pub fn include_analysis(
+    diagnostic_data: &mut DiagnosticsRunData,
+    document: &DocumentData,
+    documents: &DashMap<String, DocumentData>,
+) {
+    for include in get_includes(document) {
+        if diagnostic_data.current_number_of_problems >= diagnostic_data.maximum_number_of_problems
+        {
+            return;
+        }
+
+        match resolve_include(&document.uri, &include) {
+            Some(target) => {
+                if includes_document(&target, document, documents) {
+                    diagnostic_data.create_linter_diagnostic(
+                        include.range,
+                        DiagnosticSeverity::WARNING,
+                        DiagnosticsCode::IncludeCycle.into_i32(),
+                        format!(
+                            "'{}' includes this document again, clingo skips files that are already included",
+                            include.path
+                        ),
+                    );
+                }
+            }
+            // Libraries of clingo like <incmode> are not files we can find
+            None if include.is_system => {}
+            None => diagnostic_data.create_linter_diagnostic(
+                include.range,
+                DiagnosticSeverity::ERROR,
+                DiagnosticsCode::MissingInclude.into_i32(),
+                format!("'{}' does not exist", include.path),
+            ),
+        }
+    }
+}
+
+/**
+ * Check if the file directly or indirectly includes the document, the included files are looked up in the known documents
+ */
+fn includes_document(
+    target: &Url,
+    document: &DocumentData,
+    documents: &DashMap<String, DocumentData>,
+) -> bool {
+    let mut visited: HashSet<Url> = HashSet::new();
+    let mut stack = vec![target.clone()];
+
+    while let Some(uri) = stack.pop() {
+        if uri == document.uri {
+            return true;
+        }
+        if !visited.insert(uri.clone()) {
+            continue;
+        }
+
+        let includes = match documents.get(uri.as_str()) {
+            Some(other) => get_includes(other.value()),
+            None => continue,
+        };
+        stack.extend(
+            includes
+                .iter()
+                .filter_map(|include| resolve_include(&uri, include)),
+        );
+    }
+
+    false
+}
+
+#[cfg(test)]
+use crate::test_utils::{create_test_document, TestDirectory};
+
+#[cfg(test)]
+fn get_include_messages(source: &str, others: &[(&str, &str)]) -> Vec<String> {
+    let directory = TestDirectory::new();
+    let documents = DashMap::new();
+    for (name, other_source) in others {
+        let mut other = create_test_document(other_source.to_string());
+        other.uri = Url::from_file_path(directory.write(name, other_source)).unwrap();
+        documents.insert(other.uri.to_string(), other);
+    }
+
+    let mut doc = create_test_document(source.to_string());
+    doc.uri = Url::from_file_path(directory.write("main.lp", source)).unwrap();
+
+    let mut diags = DiagnosticsRunData::create_test_diagnostics();
+    include_analysis(&mut diags, &doc, &documents);
+
+    diags
+        .total_diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.clone())
+        .collect()
+}
+
+#[test]
+fn missing_includes_should_be_detected() {
+    assert_eq!(
+        get_include_messages(
+            "#include \"base.lp\". #include \"missing.lp\". #include <incmode>.",
+            &[("base.lp", "a.")]
+        ),
+        vec!["'missing.lp' does not exist"]
+    );
+}
+
+#[test]
+fn include_cycles_should_be_detected() {
+    assert_eq!(
+        get_include_messages(
+            "#include \"a.lp\". #include \"c.lp\". #include \"main.lp\".",
+            &[
+                ("a.lp", "#include \"b.lp\"."),
+                ("b.lp", "#include \"main.lp\"."),
+                ("c.lp", "#include \"b.lp\". #include \"c.lp\".")
+            ]
+        ),
+        vec![
+            "'a.lp' includes this document again, clingo skips files that are already included",
+            "'c.lp' includes this document again, clingo skips files that are already included",
+            "'main.lp' includes this document again, clingo skips files that are already included"
+        ]
+    );
+}
@@ -1,19 +1,51 @@
+use dashmap::DashMap;
+
+use crate::diagnostics::constant_analysis::constant_analysis;
+use crate::diagnostics::include_analysis::include_analysis;
+use crate::diagnostics::predicate_analysis::predicate_analysis;
 use crate::diagnostics::statement_analysis::statement_analysis;
+use crate::diagnostics::term_analysis::term_analysis;
+use crate::diagnostics::variable_analysis::variable_analysis;
 use crate::document::DocumentData;
+use crate::workspace::Program;
 
 use self::{diagnostic_run_data::DiagnosticsRunData, tree_error_analysis::search_for_tree_error};
 
-mod diagnostic_codes;
+mod constant_analysis;
+pub mod diagnostic_codes;
 mod diagnostic_run_data;
-mod statement_analysis;
+mod include_analysis;
+mod predicate_analysis;
+pub mod statement_analysis;
+mod term_analysis;
 mod tree_error_analysis;
 pub mod tree_utils;
+pub mod variable_analysis;
 
 /**
- * Run the selected diagnostics on the parse tree
+ * Run the selected diagnostics on the parse tree, every known document is part of the program
  */
 pub fn run_diagnostics(
     document: DocumentData,
+    documents: &DashMap<String, DocumentData>,
+    maximum_number_of_problems: u32,
+) -> Vec<tower_lsp::lsp_types::Diagnostic> {
+    run_program_diagnostics(
+        document,
+        documents,
+        &[Program::default()],
+        maximum_number_of_problems,
+    )
+}
+
+/**
+ * Run the selected diagnostics for a document that is part of several programs, like the file groups of a project.
+ * The lints that depend on the other documents of the program run for every program, a problem is reported if it occurs in any of them
+ */
+pub fn run_program_diagnostics(
+    document: DocumentData,
+    documents: &DashMap<String, DocumentData>,
+    programs: &[Program],
     maximum_number_of_problems: u32,
 ) -> Vec<tower_lsp::lsp_types::Diagnostic> {
     //Setup the diagnostics run data object to be used for this diagnostics run
@@ -27,5 +59,33 @@ pub fn run_diagnostics(
 
     statement_analysis(&mut diagnostic_data, &document);
 
-    diagnostic_data.total_diagnostics
+    variable_analysis(&mut diagnostic_data, &document);
+
+    term_analysis(&mut diagnostic_data, &document);
+
+    for program in programs {
+        let mut program_data = DiagnosticsRunData {
+            maximum_number_of_problems: diagnostic_data
+                .maximum_number_of_problems
+                .saturating_sub(diagnostic_data.current_number_of_problems),
+            current_number_of_problems: 0,
+            total_diagnostics: Vec::new(),
+        };
+
+        predicate_analysis(&mut program_data, &document, documents, program);
+
+        constant_analysis(&mut program_data, &document, documents, program);
+
+        // A problem that occurs in several programs is only reported once
+        for diagnostic in program_data.total_diagnostics {
+            if !diagnostic_data.total_diagnostics.contains(&diagnostic) {
+                diagnostic_data.total_diagnostics.push(diagnostic);
+                diagnostic_data.current_number_of_problems += 1;
+            }
+        }
+    }
+
+    include_analysis(&mut diagnostic_data, &document, documents);
+
+    diagnostic_data.into_diagnostics(&document)
 }
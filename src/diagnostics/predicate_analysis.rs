@@ -0,0 +1,394 @@
+use std::collections::HashSet;
+
+use dashmap::DashMap;
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+use crate::{
+    document::DocumentData,
+    semantics::predicate_occurence_semantics::{
+        PredicateOccurenceLocation, PredicateOccurenceSemantics,
+    },
+    workspace::Program,
+};
+
+#[cfg(test)]
+use crate::test_utils::create_test_document;
+
+use super::{diagnostic_codes::DiagnosticsCode, diagnostic_run_data::DiagnosticsRunData};
+
+/**
+ * Analyze where the predicates of the encoding are used and defined
+ */
+pub fn predicate_analysis(
+    diagnostic_data: &mut DiagnosticsRunData,
+    document: &DocumentData,
+    documents: &DashMap<String, DocumentData>,
+    program: &Program,
+) {
+    let defined = get_defined_predicates(document, documents, program);
+
+    check_undefined_predicates(diagnostic_data, document, &defined);
+    check_arity_mismatches(diagnostic_data, document, &defined);
+    check_unused_predicates(diagnostic_data, document, documents, program);
+}
+
+/**
+ * Find all predicates that occur in a head, #defined or #external statement
+ */
+fn get_defined_predicates(
+    document: &DocumentData,
+    documents: &DashMap<String, DocumentData>,
+    program: &Program,
+) -> HashSet<(String, usize)> {
+    let mut defined = HashSet::new();
+
+    program.for_each_document(document, documents, |document| {
+        for predicate in document.semantics.predicate_semantics.predicates.iter() {
+            // External atoms occur in the head of their statement
+            let is_defined = predicate.value().iter().any(|occurence| {
+                occurence.is_atom
+                    && matches!(
+                        occurence.location,
+                        PredicateOccurenceLocation::Head | PredicateOccurenceLocation::Defined
+                    )
+            });
+
+            if is_defined {
+                defined.insert(predicate.key().clone());
+            }
+        }
+    });
+
+    defined
+}
+
+/**
+ * Get the arities a predicate is defined with, except the given arity
+ */
+fn get_other_arities(
+    defined: &HashSet<(String, usize)>,
+    identifier: &str,
+    arity: usize,
+) -> Vec<usize> {
+    let mut arities: Vec<usize> = defined
+        .iter()
+        .filter(|(other, other_arity)| other == identifier && *other_arity != arity)
+        .map(|(_, other_arity)| *other_arity)
+        .collect();
+    arities.sort();
+
+    arities
+}
+
+/**
+ * Find predicates that are used, but never occur in a head, #defined or #external statement.
+ * If the predicate is defined with another arity, check_arity_mismatches reports it instead
+ */
+fn check_undefined_predicates(
+    diagnostic_data: &mut DiagnosticsRunData,
+    document: &DocumentData,
+    defined: &HashSet<(String, usize)>,
+) {
+    let mut undefined: Vec<(String, usize, PredicateOccurenceSemantics)> = Vec::new();
+
+    for predicate in document.semantics.predicate_semantics.predicates.iter() {
+        let (identifier, arity) = predicate.key();
+        if defined.contains(predicate.key())
+            || !get_other_arities(defined, identifier, *arity).is_empty()
+        {
+            continue;
+        }
+
+        for occurence in predicate.value() {
+            if occurence.is_atom
+                && matches!(
+                    occurence.location,
+                    PredicateOccurenceLocation::Body | PredicateOccurenceLocation::Condition
+                )
+            {
+                undefined.push((identifier.clone(), *arity, occurence.clone()));
+            }
+        }
+    }
+
+    // Report in the order they occur in the document
+    undefined.sort_by_key(|(_, _, occurence)| occurence.range.start_byte);
+
+    for (identifier, arity, occurence) in undefined {
+        if diagnostic_data.current_number_of_problems >= diagnostic_data.maximum_number_of_problems
+        {
+            return;
+        }
+
+        diagnostic_data.create_linter_diagnostic(
+            occurence.range,
+            DiagnosticSeverity::WARNING,
+            DiagnosticsCode::UndefinedPredicate.into_i32(),
+            format!(
+                "'{}/{}' does not occur in any rule head, #defined or #external statement",
+                identifier, arity
+            ),
+        );
+    }
+}
+
+/**
+ * Find predicates that are used with an arity they are never defined with, while they are defined with another arity
+ */
+fn check_arity_mismatches(
+    diagnostic_data: &mut DiagnosticsRunData,
+    document: &DocumentData,
+    defined: &HashSet<(String, usize)>,
+) {
+    let mut mismatches: Vec<(String, usize, Vec<usize>, PredicateOccurenceSemantics)> = Vec::new();
+
+    for predicate in document.semantics.predicate_semantics.predicates.iter() {
+        let (identifier, arity) = predicate.key();
+        if defined.contains(predicate.key()) {
+            continue;
+        }
+
+        let arities = get_other_arities(defined, identifier, *arity);
+        if arities.is_empty() {
+            continue;
+        }
+
+        for occurence in predicate.value() {
+            if occurence.is_atom
+                && matches!(
+                    occurence.location,
+                    PredicateOccurenceLocation::Body
+                        | PredicateOccurenceLocation::Condition
+                        | PredicateOccurenceLocation::Show
+                )
+            {
+                mismatches.push((
+                    identifier.clone(),
+                    *arity,
+                    arities.clone(),
+                    occurence.clone(),
+                ));
+            }
+        }
+    }
+
+    mismatches.sort_by_key(|(_, _, _, occurence)| occurence.range.start_byte);
+
+    for (identifier, arity, arities, occurence) in mismatches {
+        if diagnostic_data.current_number_of_problems >= diagnostic_data.maximum_number_of_problems
+        {
+            return;
+        }
+
+        let suggestions: Vec<String> = arities
+            .iter()
+            .map(|other| format!("'{}/{}'", identifier, other))
+            .collect();
+
+        diagnostic_data.create_linter_diagnostic(
+            occurence.range,
+            DiagnosticSeverity::WARNING,
+            DiagnosticsCode::ArityMismatch.into_i32(),
+            format!(
+                "'{}/{}' is never defined, did you mean {}?",
+                identifier,
+                arity,
+                suggestions.join(" or ")
+            ),
+        );
+    }
+}
+
+/**
+ * Find predicates that are derived in a head, but are never used in a body, condition, #show or optimization statement of any document.
+ * Without any #show statement every atom is part of the output, so then nothing is reported
+ */
+fn check_unused_predicates(
+    diagnostic_data: &mut DiagnosticsRunData,
+    document: &DocumentData,
+    documents: &DashMap<String, DocumentData>,
+    program: &Program,
+) {
+    let mut used: HashSet<(String, usize)> = HashSet::new();
+    let mut has_show = false;
+
+    program.for_each_document(document, documents, |other| {
+        let root = other.tree.root_node();
+        has_show |= root.children(&mut root.walk()).any(|statement| {
+            statement.kind() == "statement"
+                && statement.child(0).map(|child| child.kind()) == Some("SHOW")
+        });
+
+        for predicate in other.semantics.predicate_semantics.predicates.iter() {
+            let is_used = predicate.value().iter().any(|occurence| {
+                occurence.is_atom
+                    && matches!(
+                        occurence.location,
+                        PredicateOccurenceLocation::Body
+                            | PredicateOccurenceLocation::Condition
+                            | PredicateOccurenceLocation::Show
+                    )
+            });
+
+            if is_used {
+                used.insert(predicate.key().clone());
+            }
+        }
+    });
+
+    if !has_show {
+        return;
+    }
+
+    let mut unused: Vec<(String, usize, PredicateOccurenceSemantics)> = Vec::new();
+    for predicate in document.semantics.predicate_semantics.predicates.iter() {
+        if used.contains(predicate.key()) {
+            continue;
+        }
+
+        let (identifier, arity) = predicate.key();
+        for occurence in predicate.value() {
+            if occurence.is_atom && occurence.location == PredicateOccurenceLocation::Head {
+                unused.push((identifier.clone(), *arity, occurence.clone()));
+            }
+        }
+    }
+
+    unused.sort_by_key(|(_, _, occurence)| occurence.range.start_byte);
+
+    for (identifier, arity, occurence) in unused {
+        if diagnostic_data.current_number_of_problems >= diagnostic_data.maximum_number_of_problems
+        {
+            return;
+        }
+
+        diagnostic_data.create_unnecessary_linter_diagnostic(
+            occurence.range,
+            DiagnosticsCode::UnusedPredicate.into_i32(),
+            format!(
+                "'{}/{}' is derived, but never used or shown",
+                identifier, arity
+            ),
+        );
+    }
+}
+
+#[test]
+fn predicates_only_used_in_the_body_should_be_undefined() {
+    let mut diags = DiagnosticsRunData::create_test_diagnostics();
+    let doc = create_test_document("a :- b(1), c. c. d :- #count{X : e(X)} > 1.".to_string());
+
+    predicate_analysis(&mut diags, &doc, &DashMap::new(), &Program::default());
+
+    let messages: Vec<String> = diags
+        .total_diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.clone())
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            "'b/1' does not occur in any rule head, #defined or #external statement",
+            "'e/1' does not occur in any rule head, #defined or #external statement"
+        ]
+    );
+}
+
+#[test]
+fn defined_and_external_predicates_should_not_be_undefined() {
+    let mut diags = DiagnosticsRunData::create_test_diagnostics();
+    let doc = create_test_document(
+        "#defined b/1. #external c(X) : d(X). d(1). a :- b(1), c(1), d(q).".to_string(),
+    );
+
+    predicate_analysis(&mut diags, &doc, &DashMap::new(), &Program::default());
+
+    assert_eq!(diags.total_diagnostics.len(), 0);
+}
+
+#[test]
+fn predicates_with_another_arity_should_be_reported_as_mismatch() {
+    let mut diags = DiagnosticsRunData::create_test_diagnostics();
+    let doc = create_test_document("#defined b/2. b(1,2,3). a :- b(1). #show b/4.".to_string());
+
+    predicate_analysis(&mut diags, &doc, &DashMap::new(), &Program::default());
+
+    let messages: Vec<String> = diags
+        .total_diagnostics
+        .iter()
+        .filter(|diagnostic| {
+            diagnostic.code
+                == Some(tower_lsp::lsp_types::NumberOrString::Number(
+                    DiagnosticsCode::ArityMismatch.into_i32(),
+                ))
+        })
+        .map(|diagnostic| diagnostic.message.clone())
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            "'b/1' is never defined, did you mean 'b/2' or 'b/3'?",
+            "'b/4' is never defined, did you mean 'b/2' or 'b/3'?"
+        ]
+    );
+}
+
+#[cfg(test)]
+fn get_unused_messages(source: &str, other: Option<&str>) -> Vec<String> {
+    let mut diags = DiagnosticsRunData::create_test_diagnostics();
+    let doc = create_test_document(source.to_string());
+    let documents = DashMap::new();
+
+    if let Some(other) = other {
+        let mut other = create_test_document(other.to_string());
+        other.uri = tower_lsp::lsp_types::Url::parse("file://other.lp").unwrap();
+        documents.insert(other.uri.to_string(), other);
+    }
+
+    check_unused_predicates(&mut diags, &doc, &documents, &Program::default());
+
+    diags
+        .total_diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.clone())
+        .collect()
+}
+
+#[test]
+fn predicates_that_are_never_used_should_be_unnecessary() {
+    assert_eq!(
+        get_unused_messages(
+            "a. b :- a. c(1). d :- #count{X : c(X)} > 0. #show d/0.",
+            None
+        ),
+        vec!["'b/0' is derived, but never used or shown"]
+    );
+}
+
+#[test]
+fn unused_predicates_should_not_be_reported_without_show() {
+    assert!(get_unused_messages("a. b :- a.", None).is_empty());
+}
+
+#[test]
+fn predicates_used_in_other_documents_should_not_be_unnecessary() {
+    // b is only shown in the other document
+    assert_eq!(
+        get_unused_messages("a. b :- a. #show a/0.", Some("#show b/0.")),
+        Vec::<String>::new()
+    );
+    // a is only used in the other document
+    assert_eq!(
+        get_unused_messages("a. #minimize{1 : b}. #show.", Some("b :- a.")),
+        Vec::<String>::new()
+    );
+    // Without the other document they are unused
+    assert_eq!(
+        get_unused_messages("a. b :- a. #show a/0.", None),
+        vec!["'b/0' is derived, but never used or shown"]
+    );
+    assert_eq!(
+        get_unused_messages("a. #minimize{1 : b}. #show.", None),
+        vec!["'a/0' is derived, but never used or shown"]
+    );
+}
@@ -29,7 +29,7 @@ pub fn statement_analysis(diagnostic_data: &mut DiagnosticsRunData, document: &D
         };
 
         if node.kind() == "statement" {
-            check_safety_of_statement(&node, &document, diagnostic_data);
+            check_safety_of_statement(&node, document, diagnostic_data);
         }
 
         if cursor.goto_first_child() {
@@ -48,11 +48,11 @@ pub fn statement_analysis(diagnostic_data: &mut DiagnosticsRunData, document: &D
  * Calculates the safe set for a set of dependencies
  */
 fn calculate_safe_set(
-    dependencies: &mut Vec<(HashSet<String>, HashSet<String>)>,
+    dependencies: &mut [(HashSet<String>, HashSet<String>)],
     global_vars: &HashSet<String>,
     global: bool,
 ) -> (HashSet<String>, HashSet<String>) {
-    let mut dep = dependencies.clone();
+    let mut dep = dependencies.to_vec();
     let mut safe_set: HashSet<String> = HashSet::new();
     let mut prev_length = 0;
     let mut vars_in_dependency: HashSet<String> = HashSet::new();
@@ -132,7 +132,7 @@ fn get_dependencies_only_occuring_in_set(
 /**
  * Find all variables occuring in a part of the encoding
  */
-fn get_variables_in_statement<'a>(
+pub fn get_variables_in_statement<'a>(
     node: &tree_sitter::Node<'a>,
     source: &'a [u8],
 ) -> std::vec::Vec<(tree_sitter::Range, &'a str, tree_sitter::Node<'a>)> {
@@ -155,13 +155,9 @@ fn get_variables_in_statement<'a>(
 }
 
 /**
- * Check if a statement is safe
+ * Calculate which variables are unsafe in a statement, both in the global context and in any local context
  */
-fn check_safety_of_statement(
-    node: &Node,
-    document: &DocumentData,
-    diagnostics: &mut DiagnosticsRunData,
-) {
+pub fn get_unsafe_variables_of_statement(node: &Node, document: &DocumentData) -> HashSet<String> {
     let statement_semantics = document
         .semantics
         .get_statement_semantics_for_node(node.id());
@@ -196,21 +192,33 @@ fn check_safety_of_statement(
         ));
     }
 
-    let unsafe_set: HashSet<String> = vars_in_dependency
+    let mut unsafe_vars: HashSet<String> = vars_in_dependency
         .difference(&global_safe_set)
         .cloned()
         .collect();
 
-    //Due to the fact that the variable locations could have changed in terms of byte range, we look for the variables again
-    let source = document.get_bytes();
-    let variable_locations = get_variables_in_statement(node, &source);
-    let mut unsafe_vars = unsafe_set.clone();
-
-    //First combine the lists of all unsafe variables in the statements
+    //Combine the lists of all unsafe variables in the statements
     for (_, set) in local_unsafe_sets {
         unsafe_vars = unsafe_vars.union(&set).cloned().collect();
     }
 
+    unsafe_vars
+}
+
+/**
+ * Check if a statement is safe
+ */
+fn check_safety_of_statement(
+    node: &Node,
+    document: &DocumentData,
+    diagnostics: &mut DiagnosticsRunData,
+) {
+    let unsafe_vars = get_unsafe_variables_of_statement(node, document);
+
+    //Due to the fact that the variable locations could have changed in terms of byte range, we look for the variables again
+    let source = document.get_bytes();
+    let variable_locations = get_variables_in_statement(node, &source);
+
     //Next we create a diagnostic for every variable we find in the variable_locations list that occurs in the unsafe_vars list
     for (location, var, _) in variable_locations {
         if unsafe_vars.contains(var) {
@@ -246,7 +254,7 @@ fn unsafe_variables_should_be_detected_no_body() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -269,7 +277,7 @@ fn unsafe_variables_should_be_detected_no_variables_in_body() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -307,7 +315,7 @@ fn safeness_should_be_blocked_by_not() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -333,7 +341,7 @@ fn safeness_should_be_blocked_by_multiple_nots() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -392,7 +400,7 @@ fn unsafeness_should_work_with_integrity_constraints() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -415,7 +423,7 @@ fn unsafe_variables_should_be_detected_in_choice_rule_with_no_body() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -453,7 +461,7 @@ fn unsafe_variables_should_be_detected_in_choice_rule() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -491,7 +499,7 @@ fn unsafe_variables_should_be_detected_in_conjunctions() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -517,7 +525,7 @@ fn unsafe_variables_should_be_detected_in_conjunctions_with_not() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -543,7 +551,7 @@ fn unsafe_variables_should_be_detected_in_show() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -581,7 +589,7 @@ fn unsafe_variables_should_be_detected_with_aggregates() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -607,7 +615,7 @@ fn unsafe_variables_should_be_detected_with_aggregates_and_disjunction() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -645,7 +653,7 @@ fn unsafe_variables_should_be_detected_within_aggregates() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -671,7 +679,7 @@ fn unsafe_variables_should_be_detected_with_disjunction() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -0,0 +1,210 @@
+use tower_lsp::lsp_types::DiagnosticSeverity;
+use tree_sitter::Node;
+
+use crate::{
+    document::DocumentData,
+    semantics::{
+        constant_semantics::ConstantSemantics,
+        term_semantic::{ArithmeticError, OperationChain, TermSemantic, TermType},
+    },
+};
+
+#[cfg(test)]
+use crate::test_utils::create_test_document;
+
+use super::{diagnostic_codes::DiagnosticsCode, diagnostic_run_data::DiagnosticsRunData};
+
+/**
+ * Walk through the parse tree and analyze the arithmetic in the terms
+ */
+pub fn term_analysis(diagnostic_data: &mut DiagnosticsRunData, document: &DocumentData) {
+    let mut stack = vec![document.tree.root_node()];
+
+    while let Some(node) = stack.pop() {
+        //If we reached the error limit stop analyzing further
+        if diagnostic_data.current_number_of_problems >= diagnostic_data.maximum_number_of_problems
+        {
+            return;
+        };
+
+        if node.kind() == "NUMBER" {
+            check_number(node, document, diagnostic_data);
+        }
+
+        // Terms of #const statements are constterm nodes, every operation chain is checked once
+        if let Some(chain) = OperationChain::from_node(node) {
+            if !OperationChain::is_part_of_chain(node) {
+                check_arithmetic(&chain, document, diagnostic_data);
+            }
+        }
+
+        let children: Vec<Node> = node.children(&mut node.walk()).collect();
+        stack.extend(children.into_iter().rev());
+    }
+}
+
+/**
+ * A division or modulo by zero is undefined and an operation that overflows has no value, clingo drops the rule instance that contains them
+ */
+fn check_arithmetic(
+    chain: &OperationChain,
+    document: &DocumentData,
+    diagnostics: &mut DiagnosticsRunData,
+) {
+    let operands: Vec<TermSemantic> = chain
+        .operands
+        .iter()
+        .map(|operand| get_operand_term(operand.term, document))
+        .collect();
+
+    let mut errors = Vec::new();
+    chain.evaluate(
+        &operands,
+        document.semantics.interval_limit,
+        &mut |operator, terms, result| {
+            let error = match terms {
+                [term] => TermSemantic::get_unary_arithmetic_error(&term.value, operator),
+                [left, right] => {
+                    TermSemantic::get_arithmetic_error(&left.value, &right.value, operator)
+                }
+                _ => None,
+            };
+
+            if let Some(error) = error {
+                errors.push((error, result.range));
+            }
+        },
+    );
+
+    for (error, range) in errors {
+        let (code, message) = match error {
+            ArithmeticError::DivisionByZero => (
+                DiagnosticsCode::DivisionByZero,
+                "divides by zero, the operation is undefined",
+            ),
+            ArithmeticError::Overflow => (
+                DiagnosticsCode::ArithmeticOverflow,
+                "overflows, the operation has no value",
+            ),
+        };
+
+        diagnostics.create_linter_diagnostic(
+            range,
+            DiagnosticSeverity::WARNING,
+            code.into_i32(),
+            format!("'{}' {}", document.get_source_for_range(range), message),
+        );
+    }
+}
+
+/**
+ * A number that does not fit in 64 bits has no value, like an operation that overflows
+ */
+fn check_number(node: Node, document: &DocumentData, diagnostics: &mut DiagnosticsRunData) {
+    let number = document.get_source_for_range(node.range());
+    if TermSemantic::parse_number(&number).is_some() {
+        return;
+    }
+
+    diagnostics.create_linter_diagnostic(
+        node.range(),
+        DiagnosticSeverity::WARNING,
+        DiagnosticsCode::ArithmeticOverflow.into_i32(),
+        format!("'{}' overflows, the number has no value", number),
+    );
+}
+
+/**
+ * The term of an operand, the values of an operand that is not constant are empty
+ */
+fn get_operand_term(node: Node, document: &DocumentData) -> TermSemantic {
+    if node.kind() == "constterm" {
+        return match ConstantSemantics::evaluate_constant_term(node, document) {
+            Some(value) => TermSemantic {
+                kind: TermType::Constant,
+                value,
+                range: node.range(),
+                ..TermSemantic::new()
+            },
+            None => TermSemantic {
+                range: node.range(),
+                ..TermSemantic::new()
+            },
+        };
+    }
+
+    document
+        .semantics
+        .get_statement_semantics_for_node(node.id())
+        .term
+}
+
+#[cfg(test)]
+fn get_arithmetic_errors(source: &str) -> Vec<String> {
+    let mut diags = DiagnosticsRunData::create_test_diagnostics();
+
+    term_analysis(&mut diags, &create_test_document(source.to_string()));
+
+    diags
+        .total_diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.clone())
+        .collect()
+}
+
+#[test]
+fn division_by_zero_should_be_detected() {
+    assert_eq!(
+        get_arithmetic_errors("a(1/0). b(X) :- c(X), X = 4/(2-2). d(5\\\\(1-1))."),
+        vec![
+            "'1/0' divides by zero, the operation is undefined",
+            "'4/(2-2)' divides by zero, the operation is undefined",
+            "'5\\\\(1-1)' divides by zero, the operation is undefined"
+        ]
+    );
+}
+
+#[test]
+fn division_by_non_zero_should_not_be_detected() {
+    assert!(get_arithmetic_errors("a(1/1). b(X) :- c(X, Y), X = 4/Y. d(-1/(0-1)).").is_empty());
+    // The parse tree groups this as 4/(2-2), but the division is evaluated first
+    assert!(get_arithmetic_errors("a(4/2-2). #const n = 4/2-2.").is_empty());
+}
+
+#[test]
+fn division_by_zero_should_be_detected_in_constants() {
+    assert_eq!(
+        get_arithmetic_errors("#const n = 1/0. #const m = 4. #const k = m\\(m-4)."),
+        vec![
+            "'1/0' divides by zero, the operation is undefined",
+            "'m\\(m-4)' divides by zero, the operation is undefined"
+        ]
+    );
+}
+
+#[test]
+fn overflow_should_be_detected() {
+    assert_eq!(
+        get_arithmetic_errors(
+            "a(2**70). b(X) :- c(X), X = 9223372036854775807+1. d(2**-1). #const n = -(2**63)."
+        ),
+        vec![
+            "'2**70' overflows, the operation has no value",
+            "'9223372036854775807+1' overflows, the operation has no value",
+            "'2**63' overflows, the operation has no value"
+        ]
+    );
+}
+
+#[test]
+fn numbers_that_do_not_fit_should_overflow() {
+    assert_eq!(
+        get_arithmetic_errors(
+            "a(99999999999999999999). b(9223372036854775807, 0x7fffffffffffffff). #const n = 0x10000000000000000+1."
+        ),
+        vec![
+            "'99999999999999999999' overflows, the number has no value",
+            "'0x10000000000000000' overflows, the number has no value"
+        ]
+    );
+}
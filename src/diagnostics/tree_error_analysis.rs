@@ -75,7 +75,7 @@ fn unknown_character_should_throw_unknown_parser_error() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -98,7 +98,7 @@ fn if_parser_expects_dot_throw_dot_parser_error() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -121,7 +121,7 @@ fn if_parser_misses_token_throw_missing_token() {
             "{:?}",
             diags
                 .total_diagnostics
-                .get(0)
+                .first()
                 .unwrap()
                 .code
                 .clone()
@@ -1,4 +1,4 @@
-use tree_sitter::TreeCursor;
+use tree_sitter::{Node, TreeCursor};
 
 /**
  * Convert a token value into a human readable string
@@ -7,8 +7,8 @@ pub fn humanize_token(token: &str) -> &str {
     match token {
         "RPAREN" => ")",
         "LPAREN" => "(",
-        "RBRACE" => "{",
-        "LBRACE" => "}",
+        "RBRACE" => "}",
+        "LBRACE" => "{",
         "COMMA" => ",",
         "DOT" => ".",
         _ => token,
@@ -33,3 +33,66 @@ pub fn retrace(mut cursor: TreeCursor) -> (TreeCursor, bool) {
     }
     (cursor, reached_root)
 }
+
+/**
+ * Find the first child of a certain kind
+ */
+pub fn get_child<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+    let mut cursor = node.walk();
+    let child = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == kind);
+    child
+}
+
+/**
+ * Find the closest ancestor (not the node itself) of a certain kind
+ */
+pub fn get_ancestor<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+    let mut parent = node.parent();
+    while let Some(current) = parent {
+        if current.kind() == kind {
+            return Some(current);
+        }
+        parent = current.parent();
+    }
+
+    None
+}
+
+/**
+ * Check if there is a node of this kind between the node and the statement
+ */
+pub fn is_below(node: Node, statement: Node, kind: &str) -> bool {
+    let mut parent = node.parent();
+    while let Some(current) = parent {
+        if current.id() == statement.id() {
+            return false;
+        }
+        if current.kind() == kind {
+            return true;
+        }
+        parent = current.parent();
+    }
+
+    false
+}
+
+/**
+ * Find all descendants (including the node itself) of a certain kind in the order they are written
+ */
+pub fn get_descendants<'a>(node: Node<'a>, kind: &str) -> Vec<Node<'a>> {
+    let mut descendants = Vec::new();
+    let mut stack = vec![node];
+
+    while let Some(current) = stack.pop() {
+        if current.kind() == kind {
+            descendants.push(current);
+        }
+
+        let children: Vec<Node> = current.children(&mut current.walk()).collect();
+        stack.extend(children.into_iter().rev());
+    }
+
+    descendants
+}
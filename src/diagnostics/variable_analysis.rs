@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use tower_lsp::lsp_types::DiagnosticSeverity;
+use tree_sitter::Node;
+
+use crate::document::DocumentData;
+
+#[cfg(test)]
+use crate::test_utils::create_test_document;
+
+use super::{
+    diagnostic_codes::DiagnosticsCode,
+    diagnostic_run_data::DiagnosticsRunData,
+    statement_analysis::{get_unsafe_variables_of_statement, get_variables_in_statement},
+};
+
+/**
+ * The scope of a variable, this is either the whole statement or a local context like an aggregate element
+ */
+#[derive(Clone, Debug)]
+pub struct VariableScope<'a> {
+    pub statement: Node<'a>,
+    pub start_byte: usize,
+    pub end_byte: usize,
+}
+
+/**
+ * Find out in which part of the statement a variable is bound.
+ * A variable that occurs globally in the statement is bound by the statement, otherwise it is bound by the aggregate element or conditional literal it occurs in
+ */
+pub fn get_scope_of_variable<'a>(
+    document: &DocumentData,
+    node: Node<'a>,
+) -> Option<VariableScope<'a>> {
+    let mut statement = node.parent()?;
+    while statement.kind() != "statement" {
+        statement = statement.parent()?;
+    }
+
+    let variable = document.get_source_for_range(node.range());
+    let statement_semantics = document
+        .semantics
+        .get_statement_semantics_for_node(statement.id());
+    let is_optimization = matches!(
+        statement.child(0).map(|child| child.kind()),
+        Some("MINIMIZE") | Some("MAXIMIZE")
+    );
+
+    // Elements of an optimization statement never share variables, otherwise global variables are bound by the statement
+    if is_optimization || !statement_semantics.global_vars.contains(&variable) {
+        let special_literal_ids: Vec<usize> = statement_semantics
+            .special_literals
+            .iter()
+            .map(|literal| literal.id)
+            .collect();
+
+        let mut parent = node.parent();
+        while let Some(current) = parent {
+            if current.id() == statement.id() {
+                break;
+            }
+
+            let is_local_context = special_literal_ids.contains(&current.id())
+                || match current.kind() {
+                    "conjunction" | "disjunction" | "disjunctionsep" | "bodyaggrelem"
+                    | "altbodyaggrelem" | "headaggrelemvec" => true,
+                    "minelemlist" | "maxelemlist" => is_optimization,
+                    _ => false,
+                };
+
+            if is_local_context {
+                // Lists of elements contain the previous elements as their first child, these are not part of this local context
+                let mut start_byte = current.start_byte();
+                if let Some(first) = current.child(0) {
+                    if first.kind() == current.kind() || first.kind() == "disjunctionsep" {
+                        start_byte = first.end_byte();
+                    }
+                }
+
+                if start_byte <= node.start_byte() {
+                    return Some(VariableScope {
+                        statement,
+                        start_byte,
+                        end_byte: current.end_byte(),
+                    });
+                }
+            }
+
+            parent = current.parent();
+        }
+    }
+
+    Some(VariableScope {
+        statement,
+        start_byte: statement.start_byte(),
+        end_byte: statement.end_byte(),
+    })
+}
+
+/**
+ * Analyze how the variables are used in each statement
+ */
+pub fn variable_analysis(diagnostic_data: &mut DiagnosticsRunData, document: &DocumentData) {
+    let root = document.tree.root_node();
+
+    for statement in root.children(&mut root.walk()) {
+        //If we reached the error limit stop analyzing further
+        if diagnostic_data.current_number_of_problems >= diagnostic_data.maximum_number_of_problems
+        {
+            return;
+        };
+
+        if statement.kind() == "statement" {
+            check_singleton_variables_of_statement(&statement, document, diagnostic_data);
+        }
+    }
+}
+
+/**
+ * Find variables that only occur once in their scope, these are most likely typos or could be replaced by '_'
+ */
+fn check_singleton_variables_of_statement(
+    node: &Node,
+    document: &DocumentData,
+    diagnostics: &mut DiagnosticsRunData,
+) {
+    // Unsafe variables are already reported
+    let unsafe_vars = get_unsafe_variables_of_statement(node, document);
+
+    let source = document.get_bytes();
+    let mut occurences: HashMap<(String, usize, usize), Vec<tree_sitter::Range>> = HashMap::new();
+
+    for (range, var, variable) in get_variables_in_statement(node, &source) {
+        // Variables starting with an underscore are intentionally unused
+        if var.starts_with('_') || unsafe_vars.contains(var) {
+            continue;
+        }
+
+        if let Some(scope) = get_scope_of_variable(document, variable) {
+            occurences
+                .entry((var.to_string(), scope.start_byte, scope.end_byte))
+                .or_default()
+                .push(range);
+        }
+    }
+
+    let mut singletons: Vec<(String, tree_sitter::Range)> = occurences
+        .into_iter()
+        .filter(|(_, ranges)| ranges.len() == 1)
+        .map(|((var, _, _), ranges)| (var, ranges[0]))
+        .collect();
+    singletons.sort_by_key(|(_, range)| range.start_byte);
+
+    for (var, range) in singletons {
+        diagnostics.create_linter_diagnostic(
+            range,
+            DiagnosticSeverity::WARNING,
+            DiagnosticsCode::SingletonVariable.into_i32(),
+            format!(
+                "'{}' only occurs once, use '_' if the value is not needed",
+                var
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+fn get_singletons(source: &str) -> Vec<String> {
+    let mut diags = DiagnosticsRunData::create_test_diagnostics();
+
+    variable_analysis(&mut diags, &create_test_document(source.to_string()));
+
+    diags
+        .total_diagnostics
+        .iter()
+        .map(|diagnostic| format!("{}", diagnostic.range.start.character))
+        .collect()
+}
+
+#[test]
+fn variables_occuring_once_should_be_singletons() {
+    assert_eq!(get_singletons("a(X) :- b(X, Y)."), vec!["13"]);
+    assert!(get_singletons("a(X) :- b(X, _Y), c(_).").is_empty());
+}
+
+#[test]
+fn unsafe_variables_should_not_be_singletons() {
+    assert!(get_singletons("a(X) :- b.").is_empty());
+}
+
+#[test]
+fn singletons_should_respect_local_scopes() {
+    assert!(get_singletons("a(N) :- N = #count{X : b(X)}.").is_empty());
+    assert_eq!(
+        get_singletons("a(N) :- N = #count{X : b(X, Y)}, c(X)."),
+        vec!["28"]
+    );
+    assert_eq!(
+        get_singletons("a :- c(X) : d(X, Z); e(X) : f(X)."),
+        vec!["17"]
+    );
+}
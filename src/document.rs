@@ -3,10 +3,54 @@ use std::time::Instant;
 use log::info;
 use ropey::Rope;
 use rust_lapper::{Interval, Lapper};
-use tower_lsp::lsp_types::{Position, TextDocumentContentChangeEvent, Url};
+use tower_lsp::lsp_types::{
+    ClientCapabilities, Position, PositionEncodingKind, TextDocumentContentChangeEvent, Url,
+};
 use tree_sitter::{InputEdit, Parser, Point, Range, Tree};
 
-use crate::semantics::{analyze_tree, encoding_semantic::EncodingSemantics};
+use crate::{
+    configuration::ServerConfiguration,
+    semantics::{analyze_tree, encoding_semantic::EncodingSemantics},
+};
+
+/**
+ * How the characters of a position from the client are counted, tree-sitter always counts bytes
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum PositionEncoding {
+    Utf8,
+    /**
+     * The encoding every client supports
+     */
+    #[default]
+    Utf16,
+}
+
+impl PositionEncoding {
+    /**
+     * Choose the encoding from the encodings the client supports, UTF-8 is preferred as it needs no conversion
+     */
+    pub fn negotiate(capabilities: &ClientCapabilities) -> PositionEncoding {
+        let supports_utf8 = capabilities
+            .general
+            .as_ref()
+            .and_then(|general| general.position_encodings.as_ref())
+            .is_some_and(|encodings| encodings.contains(&PositionEncodingKind::UTF8));
+
+        if supports_utf8 {
+            PositionEncoding::Utf8
+        } else {
+            PositionEncoding::Utf16
+        }
+    }
+
+    pub fn get_kind(self) -> PositionEncodingKind {
+        match self {
+            PositionEncoding::Utf8 => PositionEncodingKind::UTF8,
+            PositionEncoding::Utf16 => PositionEncodingKind::UTF16,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct DocumentData {
@@ -15,18 +59,58 @@ pub struct DocumentData {
     pub source: Rope,
     pub version: i32,
     pub semantics: EncodingSemantics,
+    pub position_encoding: PositionEncoding,
+    /**
+     * The byte offsets where the rows of the tree start. Tree-sitter only breaks rows at '\n',
+     * the lines of the source and the client also break at a single '\r' and at Unicode line separators
+     */
+    row_starts: Vec<usize>,
 }
 impl DocumentData {
     pub fn new(uri: Url, tree: Tree, source: Rope, version: i32) -> DocumentData {
+        let row_starts = get_row_starts(&source);
+
         DocumentData {
             uri,
             tree,
             source,
             version,
             semantics: EncodingSemantics::new(),
+            position_encoding: PositionEncoding::default(),
+            row_starts,
         }
     }
 
+    /**
+     * Parse the source of an encoding and analyze it with the default configuration
+     */
+    pub fn parse(uri: Url, source: &str) -> DocumentData {
+        DocumentData::parse_with_configuration(uri, source, &ServerConfiguration::default())
+    }
+
+    /**
+     * Parse the source of an encoding and analyze it with the constants and limits of the configuration
+     */
+    pub fn parse_with_configuration(
+        uri: Url,
+        source: &str,
+        configuration: &ServerConfiguration,
+    ) -> DocumentData {
+        let mut parser = Parser::new();
+        parser
+            .set_language(tree_sitter_clingo::language())
+            .expect("Error loading clingo grammar");
+        let tree = parser
+            .parse(source, None)
+            .expect("Parsing without a timeout always returns a tree");
+
+        let mut document = DocumentData::new(uri, tree, Rope::from_str(source), 0);
+        configuration.apply(&mut document);
+        document.generate_semantics(None);
+
+        document
+    }
+
     pub fn get_bytes(&self) -> Vec<u8> {
         let mut array = Vec::with_capacity(self.source.len_bytes());
 
@@ -37,13 +121,106 @@ impl DocumentData {
         array
     }
 
-    pub fn convert_position_to_point(position: Position) -> Point {
+    /**
+     * The char index of the end of a line in the source, before the line break
+     */
+    fn get_line_end_char(&self, line: usize) -> usize {
+        let slice = self.source.line(line);
+        let mut length = slice.len_chars();
+        while length > 0 && is_line_break(slice.char(length - 1)) {
+            length -= 1;
+        }
+
+        self.source.line_to_char(line) + length
+    }
+
+    /**
+     * Convert a position from the client to a char index in the source.
+     * Like the protocol requires, positions after the end of a line refer to the end of the line
+     */
+    pub fn convert_position_to_char(&self, position: Position) -> usize {
+        let line = position.line as usize;
+        if line >= self.source.len_lines() {
+            return self.source.len_chars();
+        }
+
+        let line_start = self.source.line_to_char(line);
+        let line_end = self.get_line_end_char(line);
+        let character = position.character as usize;
+
+        match self.position_encoding {
+            PositionEncoding::Utf8 => {
+                let byte = (self.source.char_to_byte(line_start) + character)
+                    .min(self.source.char_to_byte(line_end));
+                self.source.byte_to_char(byte)
+            }
+            PositionEncoding::Utf16 => {
+                let code_unit = (self.source.char_to_utf16_cu(line_start) + character)
+                    .min(self.source.char_to_utf16_cu(line_end));
+                self.source.utf16_cu_to_char(code_unit)
+            }
+        }
+    }
+
+    /**
+     * Convert a char index in the source to a point in the tree, which counts the bytes of the row
+     */
+    pub fn convert_char_to_point(&self, char: usize) -> Point {
+        let byte = self.source.char_to_byte(char);
+        let row = self.row_starts.partition_point(|start| *start <= byte) - 1;
+
         Point {
-            row: position.line as usize,
-            column: position.character as usize,
+            row,
+            column: byte - self.row_starts[row],
+        }
+    }
+
+    pub fn convert_position_to_point(&self, position: Position) -> Point {
+        self.convert_char_to_point(self.convert_position_to_char(position))
+    }
+
+    pub fn convert_position_to_byte(&self, position: Position) -> usize {
+        self.source
+            .char_to_byte(self.convert_position_to_char(position))
+    }
+
+    /**
+     * Convert a point in the tree to a byte index in the source, points after the end of the source refer to the end
+     */
+    pub fn convert_point_to_byte(&self, point: Point) -> usize {
+        match self.row_starts.get(point.row) {
+            Some(start) => (start + point.column).min(self.source.len_bytes()),
+            None => self.source.len_bytes(),
+        }
+    }
+
+    pub fn convert_point_to_position(&self, point: Point) -> Position {
+        let char = self.source.byte_to_char(self.convert_point_to_byte(point));
+        let line = self.source.char_to_line(char);
+
+        let character = match self.position_encoding {
+            PositionEncoding::Utf8 => {
+                self.source.char_to_byte(char) - self.source.line_to_byte(line)
+            }
+            PositionEncoding::Utf16 => {
+                self.source.char_to_utf16_cu(char)
+                    - self.source.char_to_utf16_cu(self.source.line_to_char(line))
+            }
+        };
+
+        Position {
+            line: line as u32,
+            character: character as u32,
         }
     }
 
+    pub fn convert_range(&self, range: Range) -> tower_lsp::lsp_types::Range {
+        tower_lsp::lsp_types::Range::new(
+            self.convert_point_to_position(range.start_point),
+            self.convert_point_to_position(range.end_point),
+        )
+    }
+
     pub fn get_source_for_range(&self, range: Range) -> String {
         self.source
             .byte_slice(range.start_byte..range.end_byte)
@@ -57,50 +234,57 @@ impl DocumentData {
     ) {
         let old_tree = &self.tree.clone();
         let mut changed_ranges: Vec<Interval<usize, usize>> = Vec::with_capacity(10);
+        let mut replaced = false;
 
         // Go over each change in order and apply them to to the rope
         for change in changes {
-            if change.range.is_none() {
-                info!("Got a text document change without a range: {:?}", change);
-                continue;
-            }
+            // A change without a range replaces the whole document
+            let range = match change.range {
+                Some(range) => range,
+                None => {
+                    self.source = Rope::from_str(&change.text);
+                    self.row_starts = get_row_starts(&self.source);
+                    replaced = true;
+                    continue;
+                }
+            };
 
             // Figure out where we should replace this rope
             let time = Instant::now();
-            let range = change.range.unwrap();
-            let start_char = self.source.line_to_char(range.start.line as usize)
-                + range.start.character as usize;
-            let end_char =
-                self.source.line_to_char(range.end.line as usize) + range.end.character as usize;
+            let start_char = self.convert_position_to_char(range.start);
+            let end_char = self.convert_position_to_char(range.end).max(start_char);
 
             let start_byte = self.source.char_to_byte(start_char);
             let old_end_byte = self.source.char_to_byte(end_char);
+            let start_position = self.convert_char_to_point(start_char);
+            let old_end_position = self.convert_char_to_point(end_char);
 
             //First remove the range from the rope
             self.source.remove(start_char..end_char);
 
             //Then add the new changes to the rope
             self.source.insert(start_char, &change.text);
+            self.row_starts = get_row_starts(&self.source);
 
             let new_end_char = start_char + change.text.chars().count();
             let new_end_byte = start_byte + change.text.len();
-            let new_end_line = self.source.char_to_line(new_end_char);
-            let new_end_column = new_end_byte - self.source.line_to_byte(new_end_line);
-            let new_end_position = Point {
-                row: new_end_line,
-                column: new_end_column,
-            };
+            let new_end_position = self.convert_char_to_point(new_end_char);
 
             let duration = time.elapsed();
             info!("Time needed for updating the rope: {:?}", duration);
 
+            // The old tree is parsed from scratch anyway
+            if replaced {
+                continue;
+            }
+
             let time = Instant::now();
             //Update the abstract syntax tree
             self.tree.edit(&InputEdit {
                 start_byte,
-                start_position: DocumentData::convert_position_to_point(range.start),
+                start_position,
                 old_end_byte,
-                old_end_position: DocumentData::convert_position_to_point(range.end),
+                old_end_position,
                 new_end_byte,
                 new_end_position,
             });
@@ -119,6 +303,11 @@ impl DocumentData {
             info!("Time needed for editing the tree: {:?}", duration);
         }
 
+        if replaced {
+            self.replace_tree(parser);
+            return;
+        }
+
         let time = Instant::now();
 
         self.tree = parser.parse(self.get_bytes(), Some(&self.tree)).unwrap();
@@ -208,7 +397,260 @@ impl DocumentData {
         self.generate_semantics(Some(lapper));
     }
 
+    /**
+     * Parse the whole source again without reusing the old tree and analyze the new tree from scratch
+     */
+    fn replace_tree(&mut self, parser: &mut Parser) {
+        let time = Instant::now();
+
+        self.tree = parser.parse(self.get_bytes(), None).unwrap();
+
+        let duration = time.elapsed();
+        info!(
+            "Time needed for parsing the replaced document: {:?}",
+            duration
+        );
+
+        self.semantics.reset();
+        self.generate_semantics(None);
+    }
+
     pub fn generate_semantics(&mut self, changed_ranges: Option<Lapper<usize, usize>>) {
         analyze_tree(self, &changed_ranges);
     }
 }
+
+/**
+ * The characters ropey ends a line with, '\r\n' ends a line as well
+ */
+fn is_line_break(char: char) -> bool {
+    matches!(
+        char,
+        '\n' | '\r' | '\u{0B}' | '\u{0C}' | '\u{85}' | '\u{2028}' | '\u{2029}'
+    )
+}
+
+/**
+ * The byte offsets where the rows of tree-sitter start
+ */
+fn get_row_starts(source: &Rope) -> Vec<usize> {
+    let mut starts = vec![0];
+    starts.extend(
+        source
+            .bytes()
+            .enumerate()
+            .filter(|(_, byte)| *byte == b'\n')
+            .map(|(index, _)| index + 1),
+    );
+
+    starts
+}
+
+#[cfg(test)]
+use crate::test_utils::create_test_document;
+
+#[cfg(test)]
+fn create_test_parser() -> Parser {
+    let mut parser = Parser::new();
+    parser
+        .set_language(tree_sitter_clingo::language())
+        .expect("Error loading clingo grammar");
+    parser
+}
+
+#[test]
+fn changes_without_a_range_should_replace_the_document() {
+    let mut doc = create_test_document("a(X) :- b(X). b(1).".to_string());
+    doc.semantics
+        .constant_overrides
+        .insert("n".to_string(), "3".to_string());
+
+    doc.update_document(
+        vec![TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: "c(1..n).".to_string(),
+        }],
+        &mut create_test_parser(),
+    );
+
+    assert_eq!(doc.source.to_string(), "c(1..n).");
+    assert_eq!(doc.tree.root_node().end_byte(), 8);
+    assert_eq!(
+        doc.semantics.predicate_semantics.get_signatures(),
+        vec![("c".to_string(), 1)]
+    );
+    // The configuration survives the reset of the semantics
+    assert_eq!(doc.semantics.constant_overrides["n"], "3");
+    assert!(doc.semantics.constant_semantics.values.contains_key("n"));
+}
+
+#[test]
+fn ranged_changes_after_a_replacement_should_apply_to_the_new_source() {
+    let mut doc = create_test_document("a(X) :- b(X). b(1).".to_string());
+
+    doc.update_document(
+        vec![
+            TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: "c(1).\nd :- c(2).".to_string(),
+            },
+            TextDocumentContentChangeEvent {
+                range: Some(tower_lsp::lsp_types::Range::new(
+                    Position::new(1, 0),
+                    Position::new(1, 1),
+                )),
+                range_length: None,
+                text: "e".to_string(),
+            },
+        ],
+        &mut create_test_parser(),
+    );
+
+    assert_eq!(doc.source.to_string(), "c(1).\ne :- c(2).");
+    assert_eq!(
+        doc.semantics.predicate_semantics.get_signatures(),
+        vec![("c".to_string(), 1), ("e".to_string(), 0)]
+    );
+    assert!(!doc.tree.root_node().has_error());
+}
+
+#[test]
+fn positions_should_be_converted_in_the_negotiated_encoding() {
+    // 'ä' is 2 bytes and 1 UTF-16 code unit, '😀' is 4 bytes and 2 UTF-16 code units
+    let mut doc = create_test_document("% ä😀\r\nb(\"😀\", X).".to_string());
+    let x = Point { row: 1, column: 10 };
+
+    assert_eq!(doc.position_encoding, PositionEncoding::Utf16);
+    assert_eq!(doc.convert_point_to_position(x), Position::new(1, 8));
+    assert_eq!(doc.convert_position_to_point(Position::new(1, 8)), x);
+    assert_eq!(
+        doc.convert_point_to_position(Point { row: 0, column: 8 }),
+        Position::new(0, 5)
+    );
+    // Positions after the end of a line refer to the end of the line, not the next line
+    assert_eq!(
+        doc.convert_position_to_point(Position::new(0, 40)),
+        Point { row: 0, column: 8 }
+    );
+    assert_eq!(
+        doc.convert_position_to_byte(Position::new(5, 0)),
+        doc.source.len_bytes()
+    );
+
+    doc.position_encoding = PositionEncoding::Utf8;
+    assert_eq!(doc.convert_point_to_position(x), Position::new(1, 10));
+    assert_eq!(doc.convert_position_to_point(Position::new(1, 10)), x);
+}
+
+#[test]
+fn lines_should_also_end_at_a_single_carriage_return() {
+    // The tree only starts a new row after '\n', the client also starts a new line after '\r' and U+2028
+    let mut doc = create_test_document("a.\rb(X) :- c(X).\u{2028}c(1).\nd.".to_string());
+    let x = Point { row: 0, column: 5 };
+    let d = Point { row: 1, column: 0 };
+
+    assert_eq!(doc.convert_point_to_position(x), Position::new(1, 2));
+    assert_eq!(doc.convert_position_to_point(Position::new(1, 2)), x);
+    assert_eq!(doc.convert_point_to_position(d), Position::new(3, 0));
+    assert_eq!(doc.convert_position_to_point(Position::new(3, 0)), d);
+    // Positions after the end of a line do not move to the next line
+    assert_eq!(
+        doc.convert_position_to_point(Position::new(0, 10)),
+        Point { row: 0, column: 2 }
+    );
+    assert_eq!(
+        doc.convert_position_to_point(Position::new(2, 10)),
+        Point { row: 0, column: 24 }
+    );
+
+    doc.update_document(
+        vec![TextDocumentContentChangeEvent {
+            range: Some(tower_lsp::lsp_types::Range::new(
+                Position::new(1, 2),
+                Position::new(1, 3),
+            )),
+            range_length: None,
+            text: "Y".to_string(),
+        }],
+        &mut create_test_parser(),
+    );
+
+    assert_eq!(doc.source.to_string(), "a.\rb(Y) :- c(X).\u{2028}c(1).\nd.");
+    assert_eq!(
+        doc.tree.root_node().to_sexp(),
+        create_test_document(doc.source.to_string())
+            .tree
+            .root_node()
+            .to_sexp()
+    );
+    assert_eq!(doc.convert_point_to_position(d), Position::new(3, 0));
+}
+
+#[test]
+fn changes_should_be_applied_at_utf16_positions() {
+    let mut doc = create_test_document("% ä😀\na(\"😀\", X) :- b(X).".to_string());
+
+    // Replace X with Y in both places, starting with the last one so the first position stays valid
+    doc.update_document(
+        vec![
+            TextDocumentContentChangeEvent {
+                range: Some(tower_lsp::lsp_types::Range::new(
+                    Position::new(1, 16),
+                    Position::new(1, 17),
+                )),
+                range_length: None,
+                text: "Y".to_string(),
+            },
+            TextDocumentContentChangeEvent {
+                range: Some(tower_lsp::lsp_types::Range::new(
+                    Position::new(1, 8),
+                    Position::new(1, 9),
+                )),
+                range_length: None,
+                text: "Y".to_string(),
+            },
+        ],
+        &mut create_test_parser(),
+    );
+
+    assert_eq!(doc.source.to_string(), "% ä😀\na(\"😀\", Y) :- b(Y).");
+    assert!(!doc.tree.root_node().has_error());
+    assert_eq!(
+        doc.tree.root_node().to_sexp(),
+        create_test_document(doc.source.to_string())
+            .tree
+            .root_node()
+            .to_sexp()
+    );
+}
+
+#[test]
+fn utf8_should_be_preferred_if_the_client_supports_it() {
+    use tower_lsp::lsp_types::GeneralClientCapabilities;
+
+    let create_capabilities = |encodings: Vec<PositionEncodingKind>| ClientCapabilities {
+        general: Some(GeneralClientCapabilities {
+            position_encodings: Some(encodings),
+            ..GeneralClientCapabilities::default()
+        }),
+        ..ClientCapabilities::default()
+    };
+
+    assert_eq!(
+        PositionEncoding::negotiate(&create_capabilities(vec![
+            PositionEncodingKind::UTF16,
+            PositionEncodingKind::UTF8
+        ])),
+        PositionEncoding::Utf8
+    );
+    assert_eq!(
+        PositionEncoding::negotiate(&create_capabilities(vec![PositionEncodingKind::UTF32])),
+        PositionEncoding::Utf16
+    );
+    assert_eq!(
+        PositionEncoding::negotiate(&ClientCapabilities::default()),
+        PositionEncoding::Utf16
+    );
+}
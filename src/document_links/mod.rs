@@ -0,0 +1,51 @@
+use tower_lsp::lsp_types::DocumentLink;
+
+use crate::{
+    document::DocumentData,
+    workspace::{get_includes, resolve_include},
+};
+
+/**
+ * Make the paths of #include directives clickable, only files that exist get a link
+ */
+pub fn check_document_links(document: &DocumentData) -> Option<Vec<DocumentLink>> {
+    let links = get_includes(document)
+        .iter()
+        .filter_map(|include| {
+            let target = resolve_include(&document.uri, include)?;
+
+            Some(DocumentLink {
+                range: document.convert_range(include.range),
+                target: Some(target),
+                tooltip: Some(format!("Open {}", include.path)),
+                data: None,
+            })
+        })
+        .collect();
+
+    Some(links)
+}
+
+#[cfg(test)]
+use crate::test_utils::{create_test_document, TestDirectory};
+
+#[test]
+fn existing_includes_should_be_linked() {
+    let directory = TestDirectory::new();
+    directory.write("encoding/base.lp", "a.");
+
+    let mut doc = create_test_document(
+        "#include \"encoding/base.lp\".\n#include \"missing.lp\". #include <incmode>.".to_string(),
+    );
+    doc.uri = tower_lsp::lsp_types::Url::from_file_path(directory.join("main.lp")).unwrap();
+
+    let links = check_document_links(&doc).unwrap();
+
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].range.start.character, 10);
+    assert_eq!(links[0].range.end.character, 26);
+    assert_eq!(
+        links[0].target,
+        tower_lsp::lsp_types::Url::from_file_path(directory.join("encoding/base.lp")).ok()
+    );
+}
@@ -0,0 +1,468 @@
+use tower_lsp::lsp_types::{FormattingOptions, FormattingProperty, Position, Range, TextEdit};
+use tree_sitter::Node;
+
+use crate::document::DocumentData;
+
+/**
+ * Options that influence how the formatter lays out statements
+ */
+#[derive(Clone, Debug)]
+pub struct FormatterOptions {
+    pub line_width: usize,
+    pub indent: String,
+}
+
+impl FormatterOptions {
+    pub const DEFAULT_LINE_WIDTH: usize = 80;
+
+    /**
+     * Create the options from the options the client sent, the line width can be set with the "lineWidth" property
+     */
+    pub fn from_formatting_options(options: &FormattingOptions) -> FormatterOptions {
+        let line_width = match options.properties.get("lineWidth") {
+            Some(FormattingProperty::Number(width)) if *width > 0 => *width as usize,
+            _ => FormatterOptions::DEFAULT_LINE_WIDTH,
+        };
+
+        let indent = if options.insert_spaces {
+            " ".repeat(options.tab_size as usize)
+        } else {
+            "\t".to_string()
+        };
+
+        FormatterOptions { line_width, indent }
+    }
+}
+
+impl Default for FormatterOptions {
+    fn default() -> Self {
+        FormatterOptions {
+            line_width: FormatterOptions::DEFAULT_LINE_WIDTH,
+            indent: "    ".to_string(),
+        }
+    }
+}
+
+/**
+ * What to put between two tokens
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Separator {
+    Nothing,
+    Space,
+    Newline,
+    BlankLine,
+}
+
+/**
+ * Format the entire document, returns None if the document contains syntax errors
+ */
+pub fn check_formatting(
+    document: &DocumentData,
+    options: &FormatterOptions,
+) -> Option<Vec<TextEdit>> {
+    if has_syntax_errors(document) {
+        return None;
+    }
+
+    let root = document.tree.root_node();
+    let items: Vec<Node> = root.children(&mut root.walk()).collect();
+
+    let mut formatted = format_items(document, &items, options);
+    if !formatted.is_empty() {
+        formatted.push('\n');
+    }
+
+    // Replace everything up to the end of the last line
+    let end = document
+        .convert_point_to_position(document.convert_char_to_point(document.source.len_chars()));
+
+    Some(vec![TextEdit::new(
+        Range::new(Position::new(0, 0), end),
+        formatted,
+    )])
+}
+
+/**
+ * Format all statements and comments that overlap with the range, returns None if the document contains syntax errors
+ */
+pub fn check_range_formatting(
+    document: &DocumentData,
+    range: Range,
+    options: &FormatterOptions,
+) -> Option<Vec<TextEdit>> {
+    if has_syntax_errors(document) {
+        return None;
+    }
+
+    let start = document.convert_position_to_point(range.start);
+    let end = document.convert_position_to_point(range.end);
+
+    let root = document.tree.root_node();
+    let items: Vec<Node> = root
+        .children(&mut root.walk())
+        .filter(|item| item.end_position() >= start && item.start_position() <= end)
+        .collect();
+
+    let (first, last) = match (items.first(), items.last()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return Some(Vec::new()),
+    };
+
+    Some(vec![TextEdit::new(
+        Range::new(
+            document.convert_point_to_position(first.start_position()),
+            document.convert_point_to_position(last.end_position()),
+        ),
+        format_items(document, &items, options),
+    )])
+}
+
+/**
+ * The formatter only works on a valid parse tree, otherwise it could change the meaning of the encoding
+ */
+fn has_syntax_errors(document: &DocumentData) -> bool {
+    !document.semantics.syntax.get_errors().is_empty()
+        || !document.semantics.syntax.get_missing().is_empty()
+}
+
+/**
+ * Format a list of consecutive top level statements and comments
+ */
+fn format_items(document: &DocumentData, items: &[Node], options: &FormatterOptions) -> String {
+    let mut output = String::new();
+    let mut previous: Option<Node> = None;
+
+    for item in items {
+        if let Some(previous) = previous {
+            let separator = if item.start_position().row - previous.end_position().row >= 2 {
+                Separator::BlankLine
+            } else if item.start_position().row == previous.end_position().row
+                && item.kind() == "comment"
+            {
+                Separator::Space
+            } else {
+                Separator::Newline
+            };
+            push_separator(&mut output, separator, "");
+        }
+
+        if item.kind() == "statement" {
+            output.push_str(&format_statement(document, *item, options));
+        } else {
+            output.push_str(document.get_source_for_range(item.range()).trim_end());
+        }
+
+        previous = Some(*item);
+    }
+
+    output
+}
+
+/**
+ * Format a single statement, rules that do not fit on a line get each body literal on its own line
+ */
+fn format_statement(
+    document: &DocumentData,
+    statement: Node,
+    options: &FormatterOptions,
+) -> String {
+    // Scripts and theories have their own syntax, we leave them as they are
+    if is_verbatim(statement) {
+        return document.get_source_for_range(statement.range());
+    }
+
+    let tokens = get_tokens(statement);
+
+    let single_line = render_tokens(document, &tokens, false, options, true);
+    let break_body = single_line.chars().count() > options.line_width
+        && statement
+            .children(&mut statement.walk())
+            .any(|child| child.kind() == "bodydot");
+
+    render_tokens(document, &tokens, break_body, options, false)
+}
+
+/**
+ * Put the tokens after each other with the correct separators
+ */
+fn render_tokens(
+    document: &DocumentData,
+    tokens: &[Node],
+    break_body: bool,
+    options: &FormatterOptions,
+    skip_comments: bool,
+) -> String {
+    let mut output = String::new();
+    let mut previous: Option<Node> = None;
+    let mut previous_code: Option<Node> = None;
+    let mut force_newline = false;
+
+    for token in tokens {
+        let is_comment = token.kind() == "comment";
+        if is_comment && skip_comments {
+            continue;
+        }
+
+        if let (Some(previous), Some(previous_code)) = (previous, previous_code) {
+            let mut separator = if is_comment {
+                // Comments stay behind the token they were behind, or on their own line
+                if token.start_position().row == previous.end_position().row {
+                    Separator::Space
+                } else {
+                    Separator::Newline
+                }
+            } else if break_body && is_body_break(previous_code) {
+                Separator::Newline
+            } else if previous.kind() == "comment" {
+                get_separator(previous_code, *token).max(Separator::Space)
+            } else {
+                get_separator(previous_code, *token)
+            };
+
+            if force_newline {
+                separator = separator.max(Separator::Newline);
+            }
+
+            push_separator(&mut output, separator, &options.indent);
+        }
+
+        output.push_str(document.get_source_for_range(token.range()).trim_end());
+
+        force_newline = is_comment && !is_block_comment(document, *token);
+        previous = Some(*token);
+        if !is_comment {
+            previous_code = Some(*token);
+        }
+    }
+
+    output
+}
+
+fn push_separator(output: &mut String, separator: Separator, indent: &str) {
+    match separator {
+        Separator::Nothing => {}
+        Separator::Space => output.push(' '),
+        Separator::Newline => {
+            output.push('\n');
+            output.push_str(indent);
+        }
+        Separator::BlankLine => {
+            output.push_str("\n\n");
+            output.push_str(indent);
+        }
+    }
+}
+
+/**
+ * The rules for spacing between two tokens of a statement
+ */
+fn get_separator(previous: Node, next: Node) -> Separator {
+    let previous_kind = previous.kind();
+    let next_kind = next.kind();
+
+    if matches!(
+        next_kind,
+        "COMMA" | "SEM" | "DOT" | "RPAREN" | "RBRACK" | "RBRACE" | "CODE"
+    ) || matches!(previous_kind, "LPAREN" | "LBRACK" | "LBRACE")
+    {
+        return Separator::Nothing;
+    }
+
+    // Function symbols and aggregates stick to their brackets
+    if (next_kind == "LPAREN" && previous_kind == "identifier")
+        || (next_kind == "LBRACE"
+            && matches!(
+                previous_kind,
+                "COUNT" | "SUM" | "SUMP" | "MIN" | "MAX" | "MINIMIZE" | "MAXIMIZE"
+            ))
+    {
+        return Separator::Nothing;
+    }
+
+    if matches!(previous_kind, "DOTS" | "AT") || matches!(next_kind, "DOTS" | "AT") {
+        return Separator::Nothing;
+    }
+
+    // Signatures like p/2
+    if (previous_kind == "SLASH" || next_kind == "SLASH")
+        && next.parent().map(|parent| parent.kind()) == Some("statement")
+    {
+        return Separator::Nothing;
+    }
+
+    if is_unary_operator(previous) {
+        return Separator::Nothing;
+    }
+
+    // Absolute values like |X|
+    if previous_kind == "VBAR" && is_first_child_of_term(previous) {
+        return Separator::Nothing;
+    }
+    if next_kind == "VBAR" && next.parent().map(|parent| parent.kind()) == Some("term") {
+        return Separator::Nothing;
+    }
+
+    Separator::Space
+}
+
+/**
+ * Check if a token is an operator like the minus in -X or -p(X)
+ */
+fn is_unary_operator(node: Node) -> bool {
+    if !matches!(node.kind(), "SUB" | "BNOT") {
+        return false;
+    }
+
+    match node.parent() {
+        Some(parent) if parent.kind() == "statement" => true,
+        Some(parent) => parent.child(0) == Some(node),
+        None => false,
+    }
+}
+
+fn is_first_child_of_term(node: Node) -> bool {
+    match node.parent() {
+        Some(parent) => parent.kind() == "term" && parent.child(0) == Some(node),
+        None => false,
+    }
+}
+
+/**
+ * A rule is broken up after the if and after each separator between body literals
+ */
+fn is_body_break(node: Node) -> bool {
+    match node.kind() {
+        "IF" | "WIF" => node.parent().map(|parent| parent.kind()) == Some("statement"),
+        "COMMA" | "SEM" => node.parent().map(|parent| parent.kind()) == Some("bodycomma"),
+        _ => false,
+    }
+}
+
+fn is_block_comment(document: &DocumentData, node: Node) -> bool {
+    document
+        .get_source_for_range(node.range())
+        .starts_with("%*")
+}
+
+/**
+ * Check if a statement should not be touched by the formatter
+ */
+fn is_verbatim(statement: Node) -> bool {
+    let mut stack = vec![statement];
+    while let Some(node) = stack.pop() {
+        if node.kind().starts_with("theory") || node.kind().starts_with("CSP") {
+            return true;
+        }
+        if matches!(node.kind(), "SCRIPT" | "THEORY") {
+            return true;
+        }
+        stack.extend(node.children(&mut node.walk()));
+    }
+
+    false
+}
+
+/**
+ * Get all tokens of a node in order, numbers, strings and optimization directives are a single token
+ */
+fn get_tokens(node: Node) -> Vec<Node> {
+    let mut tokens = Vec::new();
+    let mut stack = vec![node];
+
+    while let Some(current) = stack.pop() {
+        if current.child_count() == 0
+            || matches!(
+                current.kind(),
+                "NUMBER" | "STRING" | "MINIMIZE" | "MAXIMIZE"
+            )
+        {
+            tokens.push(current);
+            continue;
+        }
+
+        let children: Vec<Node> = current.children(&mut current.walk()).collect();
+        stack.extend(children.into_iter().rev());
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+use crate::test_utils::create_test_document;
+
+#[cfg(test)]
+fn format(source: &str, line_width: usize) -> Option<String> {
+    let options = FormatterOptions {
+        line_width,
+        ..FormatterOptions::default()
+    };
+    let edits = check_formatting(&create_test_document(source.to_string()), &options)?;
+
+    Some(edits[0].new_text.clone())
+}
+
+#[test]
+fn formatting_should_normalize_spacing() {
+    assert_eq!(
+        format("a(X,Y):-b(X),not c( Y ),X!=Y,#count{ Z:d(Z) }>2.", 80).unwrap(),
+        "a(X, Y) :- b(X), not c(Y), X != Y, #count{Z : d(Z)} > 2.\n"
+    );
+    assert_eq!(
+        format("-p(-1..n).\n#show  p /1.\n:~q(X).[X@1,X]", 80).unwrap(),
+        "-p(-1..n).\n#show p/1.\n:~ q(X). [X@1, X]\n"
+    );
+}
+
+#[test]
+fn long_rules_should_have_a_literal_per_line() {
+    assert_eq!(
+        format("a(X) :- b(X), c(X); d(X).", 10).unwrap(),
+        "a(X) :-\n    b(X),\n    c(X);\n    d(X).\n"
+    );
+}
+
+#[test]
+fn formatting_should_keep_comments() {
+    assert_eq!(
+        format("% start\n\n\na :- b, % why\nc.  % end\nd.", 80).unwrap(),
+        "% start\n\na :- b, % why\n    c. % end\nd.\n"
+    );
+    assert_eq!(
+        format("a :- b, % why\nc.", 5).unwrap(),
+        "a :-\n    b, % why\n    c.\n"
+    );
+}
+
+#[test]
+fn formatting_should_be_idempotent() {
+    let source = "%* header *%\na(X):-b(X),c(X),d(X),e(X). % trailing\n\n\n{x(X):y(X)}=1:-z.\nf(|X|,X+1):-g(X),X<3,\n h.";
+
+    for width in [10, 80] {
+        let once = format(source, width).unwrap();
+        let twice = format(&once, width).unwrap();
+        assert_eq!(once, twice);
+    }
+}
+
+#[test]
+fn formatting_should_refuse_documents_with_errors() {
+    assert!(format("a :- b(.", 80).is_none());
+    assert!(format("a :- b", 80).is_none());
+}
+
+#[test]
+fn range_formatting_should_only_change_overlapping_statements() {
+    let doc = create_test_document("a:-b.\nc:-d.\ne:-f.".to_string());
+    let edits = check_range_formatting(
+        &doc,
+        Range::new(Position::new(1, 1), Position::new(1, 2)),
+        &FormatterOptions::default(),
+    )
+    .unwrap();
+
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].range.start, Position::new(1, 0));
+    assert_eq!(edits[0].range.end, Position::new(1, 5));
+    assert_eq!(edits[0].new_text, "c :- d.");
+}
@@ -1,5 +1,5 @@
+use dashmap::DashMap;
 use tower_lsp::lsp_types::{Location, Position};
-use tree_sitter::Point;
 
 use crate::{
     document::DocumentData, semantics::predicate_occurence_semantics::PredicateOccurenceLocation,
@@ -10,22 +10,110 @@ use super::get_occurences_for_predicate;
 /**
  * Check and find the definition for an predicate at this position
  */
-pub fn check_goto_definition(document: &DocumentData, position: Position) -> Option<Vec<Location>> {
-    || -> Option<Vec<Location>> {
-        let node = document.tree.root_node().descendant_for_point_range(
-            Point {
-                row: position.line as usize,
-                column: (position.character) as usize,
-            },
-            Point {
-                row: position.line as usize,
-                column: (position.character) as usize,
-            },
-        );
-
-        let ret =
-            get_occurences_for_predicate(document, node, vec![PredicateOccurenceLocation::Head]);
-
-        Some(ret)
-    }()
+pub fn check_goto_definition(
+    document: &DocumentData,
+    documents: &DashMap<String, DocumentData>,
+    position: Position,
+) -> Option<Vec<Location>> {
+    let point = document.convert_position_to_point(position);
+    let node = document
+        .tree
+        .root_node()
+        .descendant_for_point_range(point, point);
+
+    // Constants are defined by their #const statement
+    if let Some(identifier) = node.and_then(|node| {
+        document
+            .semantics
+            .constant_semantics
+            .get_constant_of_node(node, document)
+    }) {
+        let declarations: Vec<Location> = document
+            .semantics
+            .constant_semantics
+            .get_declarations(&identifier)
+            .iter()
+            .map(|declaration| {
+                Location::new(
+                    document.uri.clone(),
+                    document.convert_range(declaration.range),
+                )
+            })
+            .collect();
+        if !declarations.is_empty() {
+            return Some(declarations);
+        }
+
+        // A constant that is not declared in the document is declared in another document of the program
+        let constant = document.semantics.external_constants.get(&identifier)?;
+        let other = documents.get(constant.uri.as_str())?;
+        return Some(vec![Location::new(
+            constant.uri.clone(),
+            other.convert_range(constant.range),
+        )]);
+    }
+
+    let ret = get_occurences_for_predicate(
+        document,
+        documents,
+        node,
+        vec![PredicateOccurenceLocation::Head],
+    );
+
+    Some(ret)
+}
+
+#[cfg(test)]
+use crate::test_utils::create_test_document;
+
+#[test]
+fn constants_should_go_to_their_declaration() {
+    let doc = create_test_document("a(1..n). #const n = 3.".to_string());
+    let locations = check_goto_definition(&doc, &DashMap::new(), Position::new(0, 5)).unwrap();
+
+    assert_eq!(locations.len(), 1);
+    assert_eq!(locations[0].range.start, Position::new(0, 16));
+    assert_eq!(locations[0].range.end, Position::new(0, 17));
+}
+
+#[test]
+fn constants_should_go_to_their_declaration_in_other_documents() {
+    use crate::semantics::constant_semantics::ConstantSemantics;
+
+    let mut other = create_test_document("#const n = 3.".to_string());
+    other.uri = tower_lsp::lsp_types::Url::parse("file://consts.lp").unwrap();
+    let mut doc = create_test_document("a(1..n).".to_string());
+    doc.semantics.external_constants = ConstantSemantics::get_exported_constants(&other);
+    doc.generate_semantics(None);
+    let documents = DashMap::new();
+    documents.insert(other.uri.to_string(), other.clone());
+
+    let locations = check_goto_definition(&doc, &documents, Position::new(0, 5)).unwrap();
+
+    assert_eq!(locations.len(), 1);
+    assert_eq!(locations[0].uri, other.uri);
+    assert_eq!(locations[0].range.start, Position::new(0, 7));
+}
+
+#[test]
+fn predicates_should_go_to_their_heads() {
+    let doc = create_test_document("a(1). a(2). b :- a(X).".to_string());
+    let locations = check_goto_definition(&doc, &DashMap::new(), Position::new(0, 17)).unwrap();
+
+    assert_eq!(locations.len(), 2);
+}
+
+#[test]
+fn predicates_should_go_to_heads_in_other_documents() {
+    let doc = create_test_document("b :- a(X).".to_string());
+    let mut other = create_test_document("a(1).".to_string());
+    other.uri = tower_lsp::lsp_types::Url::parse("file://other.lp").unwrap();
+    let other_uri = other.uri.clone();
+    let documents = DashMap::new();
+    documents.insert(other.uri.to_string(), other);
+
+    let locations = check_goto_definition(&doc, &documents, Position::new(0, 5)).unwrap();
+
+    assert_eq!(locations.len(), 1);
+    assert_eq!(locations[0].uri, other_uri);
 }
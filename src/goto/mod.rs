@@ -1,4 +1,5 @@
-use tower_lsp::lsp_types::{Location, Position, Range};
+use dashmap::DashMap;
+use tower_lsp::lsp_types::Location;
 use tree_sitter::Node;
 
 use crate::{
@@ -9,13 +10,14 @@ pub mod definition;
 pub mod references;
 
 /**
- * Obtain the occurences for a specific predicate
- * identifier: The identifier of this predicate
- * arity: The arity of this predicate
+ * Obtain the occurences for a specific predicate in all documents
+ * starting_node: The node in the document of which the predicate is searched
+ * documents: The other documents of the workspace
  * locations: Which location the predicate needs to be to be counted as an occurence
  */
 pub fn get_occurences_for_predicate(
     document: &DocumentData,
+    documents: &DashMap<String, DocumentData>,
     starting_node: Option<Node>,
     locations: Vec<PredicateOccurenceLocation>,
 ) -> Vec<Location> {
@@ -35,30 +37,21 @@ pub fn get_occurences_for_predicate(
                 .get_predicates_arity_for_node(&node.unwrap().child(2).unwrap().id())
                 + 1;
 
-            for ((identifier, arity), occurences) in
-                document.semantics.predicate_semantics.predicates.clone()
-            {
-                // Find if this is the correct identifier and arity
-                if identifier == node_identifier && arity == node_arity {
-                    // Return all occurences that are in the head
-                    for occurence in occurences {
-                        if locations.contains(&occurence.location) {
-                            let range = Range::new(
-                                Position {
-                                    line: occurence.range.start_point.row as u32,
-                                    character: occurence.range.start_point.column as u32,
-                                },
-                                Position {
-                                    line: occurence.range.end_point.row as u32,
-                                    character: occurence.range.end_point.column as u32,
-                                },
-                            );
-
-                            ret.push(Location::new(document.uri.clone(), range));
-                        }
-                    }
-
-                    break;
+            // The document in the map could be outdated, so use the current one instead
+            ret.extend(get_occurences_in_document(
+                document,
+                &node_identifier,
+                node_arity,
+                &locations,
+            ));
+            for other in documents.iter() {
+                if other.uri != document.uri {
+                    ret.extend(get_occurences_in_document(
+                        other.value(),
+                        &node_identifier,
+                        node_arity,
+                        &locations,
+                    ));
                 }
             }
             break;
@@ -67,3 +60,34 @@ pub fn get_occurences_for_predicate(
     }
     ret
 }
+
+/**
+ * Obtain the occurences of a predicate in a single document
+ */
+fn get_occurences_in_document(
+    document: &DocumentData,
+    identifier: &str,
+    arity: usize,
+    locations: &[PredicateOccurenceLocation],
+) -> Vec<Location> {
+    let occurences = match document
+        .semantics
+        .predicate_semantics
+        .predicates
+        .get(&(identifier.to_string(), arity))
+    {
+        Some(occurences) => occurences.clone(),
+        None => return Vec::new(),
+    };
+
+    occurences
+        .iter()
+        .filter(|occurence| locations.contains(&occurence.location))
+        .map(|occurence| {
+            Location::new(
+                document.uri.clone(),
+                document.convert_range(occurence.range),
+            )
+        })
+        .collect()
+}
@@ -1,5 +1,5 @@
+use dashmap::DashMap;
 use tower_lsp::lsp_types::{Location, Position};
-use tree_sitter::Point;
 
 use crate::{
     document::DocumentData, semantics::predicate_occurence_semantics::PredicateOccurenceLocation,
@@ -10,28 +10,26 @@ use super::get_occurences_for_predicate;
 /**
  * Check and find the references to an predicate at this position
  */
-pub fn check_goto_references(document: &DocumentData, position: Position) -> Option<Vec<Location>> {
-    || -> Option<Vec<Location>> {
-        let node = document.tree.root_node().descendant_for_point_range(
-            Point {
-                row: position.line as usize,
-                column: (position.character) as usize,
-            },
-            Point {
-                row: position.line as usize,
-                column: (position.character) as usize,
-            },
-        );
+pub fn check_goto_references(
+    document: &DocumentData,
+    documents: &DashMap<String, DocumentData>,
+    position: Position,
+) -> Option<Vec<Location>> {
+    let point = document.convert_position_to_point(position);
+    let node = document
+        .tree
+        .root_node()
+        .descendant_for_point_range(point, point);
 
-        let ret = get_occurences_for_predicate(
-            document,
-            node,
-            vec![
-                PredicateOccurenceLocation::Body,
-                PredicateOccurenceLocation::Condition,
-            ],
-        );
+    let ret = get_occurences_for_predicate(
+        document,
+        documents,
+        node,
+        vec![
+            PredicateOccurenceLocation::Body,
+            PredicateOccurenceLocation::Condition,
+        ],
+    );
 
-        Some(ret)
-    }()
+    Some(ret)
 }
@@ -0,0 +1,417 @@
+use tower_lsp::lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind, Position};
+use tree_sitter::Node;
+
+use crate::{
+    diagnostics::{
+        statement_analysis::get_unsafe_variables_of_statement, tree_utils::get_ancestor,
+    },
+    document::DocumentData,
+    semantics::{
+        predicate_occurence_semantics::PredicateOccurenceLocation,
+        term_semantic::{OperationChain, TermOperator, TermType},
+    },
+};
+
+/**
+ * Check what is under the cursor and create a hover card for it
+ */
+pub fn check_hover(document: &DocumentData, position: Position) -> Option<Hover> {
+    let point = document.convert_position_to_point(position);
+    let node = document
+        .tree
+        .root_node()
+        .descendant_for_point_range(point, point)?;
+
+    if node.kind() == "VARIABLE" {
+        return hover_for_variable(document, node);
+    }
+
+    if let Some(hover) = hover_for_constant(document, node) {
+        return Some(hover);
+    }
+
+    if let Some(hover) = hover_for_predicate(document, node) {
+        return Some(hover);
+    }
+
+    hover_for_term(document, node)
+}
+
+/**
+ * Create a hover card for a variable, showing if it is safe and which literal provides it
+ */
+fn hover_for_variable(document: &DocumentData, node: Node) -> Option<Hover> {
+    let statement = get_ancestor(node, "statement")?;
+    let variable = document.get_source_for_range(node.range());
+
+    let mut content = format!("**{}**\n\n", variable);
+
+    if get_unsafe_variables_of_statement(&statement, document).contains(&variable) {
+        content += "unsafe: not provided by any positive literal";
+    } else {
+        content += "safe";
+
+        if let Some(literal) = get_literal_providing_variable(document, statement, &variable) {
+            content += &format!(
+                ", provided by `{}`",
+                document.get_source_for_range(literal.range())
+            );
+        }
+    }
+
+    Some(create_hover(document, content, node))
+}
+
+/**
+ * Create a hover card for a constant, showing its declaration and the value it evaluates to
+ */
+fn hover_for_constant(document: &DocumentData, node: Node) -> Option<Hover> {
+    let constants = &document.semantics.constant_semantics;
+    let identifier = constants.get_constant_of_node(node, document)?;
+
+    // A constant that is not declared in the document is declared in another document of the program
+    let declaration = match constants.get_declarations(&identifier).first() {
+        Some(declaration) => {
+            let statement = document
+                .tree
+                .root_node()
+                .descendant_for_byte_range(
+                    declaration.range.start_byte,
+                    declaration.range.end_byte,
+                )?
+                .parent()?;
+            document.get_source_for_range(statement.range())
+        }
+        None => document
+            .semantics
+            .external_constants
+            .get(&identifier)?
+            .declaration
+            .clone(),
+    };
+    let mut content = format!("**{}**\n\n`{}`", identifier, declaration);
+
+    if let Some(value) = constants.values.get(&identifier) {
+        let mut values: Vec<i64> = value.iter().copied().collect();
+        values.sort_unstable();
+
+        content += &format!(
+            "\n\nevaluates to: {{{}}}",
+            values
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+    }
+
+    Some(create_hover(document, content, node))
+}
+
+/**
+ * Create a hover card for a predicate, showing its signature and where it occurs
+ */
+fn hover_for_predicate(document: &DocumentData, node: Node) -> Option<Hover> {
+    // Only the identifier of a predicate should show the predicate information
+    if node.kind() != "identifier" {
+        return None;
+    }
+
+    let predicate = node.parent()?;
+    if (predicate.kind() != "atom" && predicate.kind() != "term")
+        || predicate.child(0)? != node
+        || (predicate.child_count() != 1 && predicate.child_count() < 3)
+    {
+        return None;
+    }
+
+    let identifier = document.get_source_for_range(node.range());
+    let mut arity = 0;
+    if predicate.child_count() >= 3 {
+        arity = document
+            .semantics
+            .predicate_semantics
+            .get_predicates_arity_for_node(&predicate.child(2)?.id())
+            + 1;
+    }
+
+    let occurences = document
+        .semantics
+        .predicate_semantics
+        .predicates
+        .get(&(identifier.clone(), arity))?
+        .clone();
+
+    let count = |location: PredicateOccurenceLocation| {
+        occurences
+            .iter()
+            .filter(|occurence| occurence.location == location)
+            .count()
+    };
+
+    let content = format!(
+        "**{}/{}**\n\nhead: {} | body: {} | condition: {}",
+        identifier,
+        arity,
+        count(PredicateOccurenceLocation::Head),
+        count(PredicateOccurenceLocation::Body),
+        count(PredicateOccurenceLocation::Condition)
+    );
+
+    Some(create_hover(document, content, predicate))
+}
+
+/**
+ * Create a hover card for an arithmetic term, showing the values it evaluates to
+ */
+fn hover_for_term(document: &DocumentData, node: Node) -> Option<Hover> {
+    let mut parent = Some(node);
+    while let Some(current) = parent {
+        if current.kind() == "statement" {
+            break;
+        }
+
+        // Operations that are part of a larger chain are not evaluated on their own
+        if matches!(current.kind(), "term" | "argvec") && !OperationChain::is_part_of_chain(current)
+        {
+            let term = document
+                .semantics
+                .get_statement_semantics_for_node(current.id())
+                .term;
+
+            if !matches!(term.operator, TermOperator::None) && term.kind == TermType::Constant {
+                let mut values: Vec<i64> = term.value.into_iter().collect();
+                values.sort_unstable();
+
+                let evaluated = if term.exceeds_limit {
+                    format!("more than {} values", document.semantics.interval_limit)
+                } else {
+                    format!(
+                        "{{{}}}",
+                        values
+                            .iter()
+                            .map(|value| value.to_string())
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    )
+                };
+                let content = format!(
+                    "**{}**\n\nevaluates to: {}",
+                    document.get_source_for_range(term.range),
+                    evaluated
+                );
+
+                return Some(create_hover(document, content, current));
+            }
+        }
+
+        parent = current.parent();
+    }
+
+    None
+}
+
+/**
+ * Find the first literal in the statement that provides a variable
+ */
+fn get_literal_providing_variable<'a>(
+    document: &DocumentData,
+    statement: Node<'a>,
+    variable: &String,
+) -> Option<Node<'a>> {
+    let mut cursor = statement.walk();
+    let mut stack = vec![statement];
+
+    while let Some(node) = stack.pop() {
+        if node.kind() == "literal" || node.kind() == "lubodyaggregate" {
+            let provides = document
+                .semantics
+                .get_statement_semantics_for_node(node.id())
+                .dependencies
+                .iter()
+                .any(|(provide, _)| provide.contains(variable));
+
+            if provides && get_ancestor(node, "head").is_none() {
+                return Some(node);
+            }
+        }
+
+        // Push the children in reverse so we visit the literals in the order they are written
+        let children: Vec<Node> = node.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+
+    None
+}
+
+/**
+ * Create a hover with markdown content that spans the given node
+ */
+fn create_hover(document: &DocumentData, content: String, node: Node) -> Hover {
+    Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: content,
+        }),
+        range: Some(document.convert_range(node.range())),
+    }
+}
+
+#[cfg(test)]
+use crate::test_utils::create_test_document;
+#[cfg(test)]
+use tower_lsp::lsp_types::Range;
+
+#[cfg(test)]
+fn get_hover_text(source: &str, position: Position) -> String {
+    let doc = create_test_document(source.to_string());
+    match check_hover(&doc, position).unwrap().contents {
+        HoverContents::Markup(content) => content.value,
+        _ => String::new(),
+    }
+}
+
+#[test]
+fn hover_on_predicate_shows_arity_and_occurences() {
+    let text = get_hover_text("edge(1,2). a :- edge(X,Y), b(X).", Position::new(0, 17));
+
+    assert!(text.contains("edge/2"));
+    assert!(text.contains("head: 1 | body: 1 | condition: 0"));
+}
+
+#[test]
+fn hover_on_variable_shows_providing_literal() {
+    let text = get_hover_text("a(X) :- not c(X), b(X).", Position::new(0, 2));
+
+    assert!(text.contains("safe, provided by `b(X)`"));
+}
+
+#[test]
+fn hover_on_unsafe_variable_shows_unsafe() {
+    let text = get_hover_text("a(X) :- not b(X).", Position::new(0, 2));
+
+    assert!(text.contains("unsafe"));
+}
+
+#[test]
+fn hover_on_arithmetic_term_shows_value() {
+    let text = get_hover_text("a(X) :- b(X), X = 2*3.", Position::new(0, 19));
+
+    assert!(text.contains("evaluates to: {6}"));
+}
+
+#[test]
+fn hover_on_arithmetic_term_shows_negative_values() {
+    let text = get_hover_text("a(X) :- b(X), X = 1-2*3.", Position::new(0, 21));
+    assert!(text.contains("evaluates to: {-5}"));
+
+    let text = get_hover_text("a(X) :- b(X), X = -1+2.", Position::new(0, 18));
+    assert!(text.contains("evaluates to: {1}"));
+
+    let text = get_hover_text("a(X) :- b(X), X = |-2|**3.", Position::new(0, 23));
+    assert!(text.contains("evaluates to: {8}"));
+
+    let text = get_hover_text("a(X) :- b(X), X = ~0 ^ (0x6 & 3).", Position::new(0, 18));
+    assert!(text.contains("evaluates to: {-3}"));
+}
+
+#[test]
+fn hover_on_interval_shows_expanded_values() {
+    // The interval has the lowest precedence, like in clingo
+    let text = get_hover_text("a(X) :- X = 1..3+1.", Position::new(0, 13));
+    assert!(text.contains("**1..3+1**"));
+    assert!(text.contains("evaluates to: {1, 2, 3, 4}"));
+
+    let text = get_hover_text("a(X) :- X = 1+1..3.", Position::new(0, 13));
+    assert!(text.contains("evaluates to: {2, 3}"));
+
+    let text = get_hover_text("a(X) :- X = 3..1.", Position::new(0, 13));
+    assert!(text.contains("evaluates to: {}"));
+}
+
+#[test]
+fn hover_on_pool_shows_all_elements() {
+    let text = get_hover_text("a(X) :- X = (1;3..4;-2).", Position::new(0, 12));
+
+    assert!(text.contains("evaluates to: {-2, 1, 3, 4}"));
+}
+
+#[test]
+fn hover_on_pool_in_arguments_shows_all_elements() {
+    let text = get_hover_text("a(1;2).", Position::new(0, 3));
+    assert!(text.contains("**1;2**"));
+    assert!(text.contains("evaluates to: {1, 2}"));
+
+    let text = get_hover_text("p(1..2;5).", Position::new(0, 6));
+    assert!(text.contains("evaluates to: {1, 2, 5}"));
+
+    // Pools of tuples have no single value
+    let doc = create_test_document("a(1,2;3).".to_string());
+    assert!(check_hover(&doc, Position::new(0, 5)).is_none());
+}
+
+#[test]
+fn hover_on_interval_above_limit_shows_limit() {
+    let text = get_hover_text("a(X) :- X = 1..100000.", Position::new(0, 13));
+    assert!(text.contains("evaluates to: more than 1000 values"));
+
+    let text = get_hover_text("a(X) :- X = (1..100000)*2.", Position::new(0, 24));
+    assert!(text.contains("evaluates to: more than 1000 values"));
+}
+
+#[test]
+fn hover_on_constant_shows_declaration_and_value() {
+    let text = get_hover_text("#const m = n*2. #const n = 3. a(m).", Position::new(0, 32));
+
+    assert!(text.contains("`#const m = n*2.`"));
+    assert!(text.contains("evaluates to: {6}"));
+
+    let text = get_hover_text("#const m = -1+2*3. a(m).", Position::new(0, 21));
+    assert!(text.contains("evaluates to: {5}"));
+}
+
+#[test]
+fn constants_should_be_substituted_in_terms() {
+    let text = get_hover_text("a(X) :- X = 1..n+1. #const n = 2.", Position::new(0, 16));
+
+    assert!(text.contains("evaluates to: {1, 2, 3}"));
+}
+
+#[test]
+fn changing_a_constant_should_evaluate_its_uses_again() {
+    let mut doc = create_test_document("#const n = 2. a(X) :- X = n+1.".to_string());
+    let mut parser = tree_sitter::Parser::new();
+    parser.set_language(tree_sitter_clingo::language()).unwrap();
+
+    doc.update_document(
+        vec![tower_lsp::lsp_types::TextDocumentContentChangeEvent {
+            range: Some(Range::new(Position::new(0, 11), Position::new(0, 12))),
+            range_length: None,
+            text: "5".to_string(),
+        }],
+        &mut parser,
+    );
+
+    match check_hover(&doc, Position::new(0, 27)).unwrap().contents {
+        HoverContents::Markup(content) => assert!(content.value.contains("evaluates to: {6}")),
+        _ => panic!("expected markdown"),
+    }
+}
+
+#[test]
+fn constants_given_on_the_command_line_should_replace_declarations() {
+    let mut doc = create_test_document(
+        "#const n = 2. #const m = 3. [override] a(X) :- X = n+m+k.".to_string(),
+    );
+    doc.semantics.constant_overrides = std::collections::HashMap::from([
+        ("n".to_string(), "5".to_string()),
+        ("m".to_string(), "7".to_string()),
+        ("k".to_string(), "10".to_string()),
+    ]);
+    doc.generate_semantics(None);
+
+    match check_hover(&doc, Position::new(0, 54)).unwrap().contents {
+        HoverContents::Markup(content) => assert!(content.value.contains("evaluates to: {18}")),
+        _ => panic!("expected markdown"),
+    }
+}
@@ -0,0 +1,49 @@
+//! The analysis behind the asp-language-server, usable without the language server protocol.
+//!
+//! A [`document::DocumentData`] holds the syntax tree of an encoding together with its [`semantics::encoding_semantic::EncodingSemantics`],
+//! which the diagnostics and the features of the language server are computed from.
+//!
+//! ```
+//! use asp_language_server::{
+//!     diagnostics::run_diagnostics, document::DocumentData, lsp_types::Url, DashMap,
+//! };
+//!
+//! let uri = Url::parse("file:///encoding.lp").unwrap();
+//! let document = DocumentData::parse(uri, "a(X) :- b(X, Y). b(1, 2). #show a/1.");
+//!
+//! // The other documents of the program, like the files the encoding includes
+//! let documents = DashMap::new();
+//! let diagnostics = run_diagnostics(document.clone(), &documents, 100);
+//! assert_eq!(diagnostics[0].message, "'Y' only occurs once, use '_' if the value is not needed");
+//!
+//! let predicates = &document.semantics.predicate_semantics;
+//! assert_eq!(predicates.get_signatures(), vec![("a".to_string(), 1), ("b".to_string(), 2)]);
+//! assert_eq!(predicates.get_occurences("b", 2).len(), 2);
+//! ```
+
+pub mod cli;
+pub mod code_actions;
+pub mod completion;
+pub mod configuration;
+pub mod diagnostics;
+pub mod document;
+pub mod document_links;
+pub mod formatting;
+pub mod goto;
+pub mod hover;
+pub mod project;
+pub mod rename;
+pub mod semantic_tokens;
+pub mod semantics;
+pub mod symbols;
+pub mod workspace;
+
+#[cfg(test)]
+mod test_utils;
+
+/**
+ * The crates that appear in the public API, so users do not have to depend on the same versions themselves
+ */
+pub use dashmap::DashMap;
+pub use tower_lsp::lsp_types;
+pub use tree_sitter;
@@ -1,51 +1,158 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
 use std::time::Instant;
 
-use completion::check_completion;
+use asp_language_server::cli;
+use asp_language_server::code_actions::check_code_actions;
+use asp_language_server::completion::check_completion;
+use asp_language_server::configuration::ServerConfiguration;
+use asp_language_server::document::{DocumentData, PositionEncoding};
+use asp_language_server::document_links::check_document_links;
+use asp_language_server::formatting::{check_formatting, check_range_formatting, FormatterOptions};
+use asp_language_server::goto::definition::check_goto_definition;
+use asp_language_server::goto::references::check_goto_references;
+use asp_language_server::hover::check_hover;
+use asp_language_server::project::PROJECT_FILE_NAME;
+use asp_language_server::rename::{check_prepare_rename, check_rename};
+use asp_language_server::semantic_tokens::{
+    calculate_semantic_tokens_delta, check_semantic_tokens, get_semantic_tokens_legend,
+};
+use asp_language_server::symbols::document_symbol::check_document_symbols;
+use asp_language_server::symbols::workspace_symbol::check_workspace_symbols;
+use asp_language_server::workspace::Workspace;
 use dashmap::DashMap;
-use diagnostics::run_diagnostics;
-use document::DocumentData;
-use goto::definition::check_goto_definition;
-use goto::references::check_goto_references;
 use log::info;
-use serde::{Deserialize, Serialize};
 use tower_lsp::jsonrpc::Result;
-use tower_lsp::lsp_types::notification::Notification;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
-
 use tree_sitter::Parser;
 
-mod completion;
-mod diagnostics;
-mod document;
-mod goto;
-mod semantics;
-
-#[cfg(test)]
-mod test_utils;
-
 struct Backend {
     client: Client,
-    document_map: DashMap<String, DocumentData>,
+    /**
+     * The open documents and the files they include or are grouped with
+     */
+    workspace: Workspace,
+    semantic_tokens_map: DashMap<String, SemanticTokens>,
+    semantic_tokens_id: AtomicUsize,
+    workspace_folders: RwLock<Vec<Url>>,
+}
+
+impl Backend {
+    /**
+     * Analyze every document again with its current configuration and publish the diagnostics of the open documents
+     */
+    async fn refresh_documents(&self) {
+        self.workspace.refresh();
+
+        for document in self.workspace.get_open_documents() {
+            let uri = document.uri.clone();
+            let version = document.version;
+            let diagnostics = self.workspace.diagnose(document, 100);
+            self.client
+                .publish_diagnostics(uri, diagnostics, Some(version))
+                .await;
+        }
+    }
+
+    /**
+     * Read the project files in the roots of the workspace folders
+     */
+    async fn load_projects(&self) {
+        let folders = self.workspace_folders.read().unwrap().clone();
+
+        for error in self.workspace.load_projects(&folders) {
+            self.client.show_message(MessageType::ERROR, error).await;
+        }
+    }
+
+    /**
+     * Remember the tokens we sent for a document, so the next request can be answered with a delta
+     */
+    fn store_semantic_tokens(&self, uri: String, data: Vec<SemanticToken>) -> SemanticTokens {
+        let id = self.semantic_tokens_id.fetch_add(1, Ordering::Relaxed);
+        let tokens = SemanticTokens {
+            result_id: Some(id.to_string()),
+            data,
+        };
+
+        self.semantic_tokens_map.insert(uri, tokens.clone());
+        tokens
+    }
 }
 
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
-    async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        #[allow(deprecated)]
+        let folders = match (params.workspace_folders, params.root_uri) {
+            (Some(folders), _) => folders.into_iter().map(|folder| folder.uri).collect(),
+            (None, Some(root)) => vec![root],
+            (None, None) => Vec::new(),
+        };
+        *self.workspace_folders.write().unwrap() = folders;
+
+        let position_encoding = PositionEncoding::negotiate(&params.capabilities);
+        self.workspace
+            .configuration
+            .write()
+            .unwrap()
+            .position_encoding = position_encoding;
+
         Ok(InitializeResult {
             server_info: None,
+            offset_encoding: None,
             capabilities: ServerCapabilities {
-                text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::INCREMENTAL,
+                position_encoding: Some(position_encoding.get_kind()),
+                // Changes are requested incrementally, changes that replace the whole document are handled as well
+                text_document_sync: Some(TextDocumentSyncCapability::Options(
+                    TextDocumentSyncOptions {
+                        open_close: Some(true),
+                        change: Some(TextDocumentSyncKind::INCREMENTAL),
+                        will_save: None,
+                        will_save_wait_until: None,
+                        save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                    },
                 )),
                 completion_provider: Some(CompletionOptions {
                     resolve_provider: Some(false),
                     trigger_characters: Some(vec!["#".to_string()]),
                     work_done_progress_options: Default::default(),
                     all_commit_characters: None,
+                    completion_item: None,
                 }),
                 definition_provider: Some(OneOf::Left(true)),
                 references_provider: Some(OneOf::Left(true)),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                document_symbol_provider: Some(OneOf::Left(true)),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Options(
+                    CodeActionOptions {
+                        code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                        work_done_progress_options: Default::default(),
+                        resolve_provider: Some(false),
+                    },
+                )),
+                document_link_provider: Some(DocumentLinkOptions {
+                    resolve_provider: Some(false),
+                    work_done_progress_options: Default::default(),
+                }),
+                document_formatting_provider: Some(OneOf::Left(true)),
+                document_range_formatting_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Right(RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                })),
+                semantic_tokens_provider: Some(
+                    SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        SemanticTokensOptions {
+                            legend: get_semantic_tokens_legend(),
+                            range: Some(false),
+                            full: Some(SemanticTokensFullOptions::Delta { delta: Some(true) }),
+                            work_done_progress_options: Default::default(),
+                        },
+                    ),
+                ),
                 workspace: Some(WorkspaceServerCapabilities {
                     workspace_folders: Some(WorkspaceFoldersServerCapabilities {
                         supported: Some(true),
@@ -62,28 +169,78 @@ impl LanguageServer for Backend {
         self.client
             .log_message(MessageType::INFO, "initialized!")
             .await;
+
+        self.load_projects().await;
     }
 
     async fn shutdown(&self) -> Result<()> {
         Ok(())
     }
 
-    async fn did_change_workspace_folders(&self, _: DidChangeWorkspaceFoldersParams) {
+    async fn did_change_workspace_folders(&self, params: DidChangeWorkspaceFoldersParams) {
         self.client
             .log_message(MessageType::INFO, "workspace folders changed!")
             .await;
+
+        {
+            let mut folders = self.workspace_folders.write().unwrap();
+            folders.retain(|folder| {
+                !params
+                    .event
+                    .removed
+                    .iter()
+                    .any(|removed| removed.uri == *folder)
+            });
+            folders.extend(params.event.added.into_iter().map(|folder| folder.uri));
+        }
+
+        self.load_projects().await;
+        self.refresh_documents().await;
     }
 
-    async fn did_change_configuration(&self, _: DidChangeConfigurationParams) {
+    async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
         self.client
             .log_message(MessageType::INFO, "configuration changed!")
             .await;
+
+        let mut configuration = ServerConfiguration::from_settings(&params.settings);
+        configuration.position_encoding = self
+            .workspace
+            .configuration
+            .read()
+            .unwrap()
+            .position_encoding;
+        if *self.workspace.configuration.read().unwrap() == configuration {
+            return;
+        }
+        *self.workspace.configuration.write().unwrap() = configuration;
+
+        // The constants and limits influence the semantics, so every document is analyzed again
+        self.refresh_documents().await;
     }
 
-    async fn did_change_watched_files(&self, _: DidChangeWatchedFilesParams) {
+    async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
         self.client
             .log_message(MessageType::INFO, "watched files have changed!")
             .await;
+
+        let mut changed = false;
+        for change in params.changes {
+            // Files that are not open are read from disk again
+            changed |= self.workspace.forget_file(&change.uri);
+
+            // A new file can be the target of an include that was missing
+            changed |= change.typ == FileChangeType::CREATED;
+
+            if change.uri.path().ends_with(PROJECT_FILE_NAME) {
+                self.load_projects().await;
+                changed = true;
+            }
+        }
+
+        if changed {
+            self.refresh_documents().await;
+        }
     }
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
@@ -107,6 +264,9 @@ impl LanguageServer for Backend {
             rope,
             params.text_document.version,
         );
+        self.workspace
+            .get_configuration_for(&params.text_document.uri)
+            .apply(&mut doc);
 
         let duration = time.elapsed();
         info!(
@@ -114,14 +274,17 @@ impl LanguageServer for Backend {
             duration
         );
         doc.generate_semantics(None);
-        self.document_map
-            .insert(params.text_document.uri.to_string(), doc.clone());
+        let doc = self.workspace.open_document(doc);
 
         // Run diagnostics for that file
         let time = Instant::now();
-        let diagnostics = run_diagnostics(doc, 100);
+        let diagnostics = self.workspace.diagnose(doc, 100);
         self.client
-            .publish_diagnostics(params.text_document.uri.clone(), diagnostics, Some(1))
+            .publish_diagnostics(
+                params.text_document.uri.clone(),
+                diagnostics,
+                Some(params.text_document.version),
+            )
             .await;
         let duration = time.elapsed();
         info!("Time needed for diagnostics: {:?}", duration);
@@ -131,7 +294,7 @@ impl LanguageServer for Backend {
         let client_copy = self.client.clone();
         let uri = params.text_document.uri.clone().to_string();
 
-        if !self.document_map.contains_key(&uri) {
+        if !self.workspace.documents.contains_key(&uri) {
             self.client
                 .log_message(
                     MessageType::ERROR,
@@ -141,7 +304,7 @@ impl LanguageServer for Backend {
             return;
         }
 
-        let mut document = self.document_map.get(&uri).unwrap().clone();
+        let mut document = self.workspace.documents.get(&uri).unwrap().clone();
 
         let mut parser = Parser::new();
         parser
@@ -149,14 +312,15 @@ impl LanguageServer for Backend {
             .expect("Error loading clingo grammar");
 
         document.update_document(params.content_changes, &mut parser);
-        let doc = document.clone();
+        document.version = params.text_document.version;
 
-        self.document_map.insert(uri, document);
+        let doc = self.workspace.open_document(document);
 
         let time = Instant::now();
-        let diagnostics = run_diagnostics(doc, 100);
+        let version = doc.version;
+        let diagnostics = self.workspace.diagnose(doc, 100);
         client_copy
-            .publish_diagnostics(params.text_document.uri.clone(), diagnostics, Some(1))
+            .publish_diagnostics(params.text_document.uri.clone(), diagnostics, Some(version))
             .await;
         let duration = time.elapsed();
         info!("Time needed for diagnostics: {:?}", duration);
@@ -175,7 +339,7 @@ impl LanguageServer for Backend {
             .log_message(MessageType::INFO, "file closed!")
             .await;
 
-        if !self.document_map.contains_key(&uri) {
+        if !self.workspace.documents.contains_key(&uri) {
             self.client
                 .log_message(
                     MessageType::ERROR,
@@ -185,8 +349,9 @@ impl LanguageServer for Backend {
             return;
         }
 
-        // Remove our information for this file
-        self.document_map.remove(&uri);
+        // Remove our information for this file, if another document includes it we read it from disk again
+        self.workspace.close_document(&params.text_document.uri);
+        self.semantic_tokens_map.remove(&uri);
     }
 
     async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
@@ -194,7 +359,7 @@ impl LanguageServer for Backend {
         let position = params.text_document_position.position;
 
         let completions = || -> Option<Vec<CompletionItem>> {
-            let document = self.document_map.get(&uri.to_string())?;
+            let document = self.workspace.documents.get(&uri.to_string())?;
 
             if let Some(context) = params.context {
                 let mut trigger_character = "".to_string();
@@ -216,9 +381,15 @@ impl LanguageServer for Backend {
     ) -> Result<Option<GotoDefinitionResponse>> {
         let uri = params.text_document_position_params.text_document.uri;
         let position = params.text_document_position_params.position;
-        if let Some(document) = self.document_map.get(&uri.to_string()) {
+        // Clone the document, as the other documents are locked while searching
+        let document = self
+            .workspace
+            .documents
+            .get(&uri.to_string())
+            .map(|document| document.value().clone());
+        if let Some(document) = document {
             return Ok(Some(GotoDefinitionResponse::Array(
-                check_goto_definition(document.value(), position).unwrap(),
+                check_goto_definition(&document, &self.workspace.documents, position).unwrap(),
             )));
         }
 
@@ -230,36 +401,213 @@ impl LanguageServer for Backend {
     async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
         let uri = params.text_document_position.text_document.uri;
         let position = params.text_document_position.position;
-        if let Some(document) = self.document_map.get(&uri.to_string()) {
-            return Ok(check_goto_references(document.value(), position));
+        let document = self
+            .workspace
+            .documents
+            .get(&uri.to_string())
+            .map(|document| document.value().clone());
+        if let Some(document) = document {
+            return Ok(check_goto_references(
+                &document,
+                &self.workspace.documents,
+                position,
+            ));
         }
 
         Result::Err(tower_lsp::jsonrpc::Error::new(
             tower_lsp::jsonrpc::ErrorCode::InternalError,
         ))
     }
-}
-#[derive(Debug, Deserialize, Serialize)]
-struct InlayHintParams {
-    path: String,
-}
 
-enum CustomNotification {}
-impl Notification for CustomNotification {
-    type Params = InlayHintParams;
-    const METHOD: &'static str = "custom/notification";
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+        if let Some(document) = self.workspace.documents.get(&uri.to_string()) {
+            return Ok(check_hover(document.value(), position));
+        }
+
+        Result::Err(tower_lsp::jsonrpc::Error::new(
+            tower_lsp::jsonrpc::ErrorCode::InternalError,
+        ))
+    }
+
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        let uri = params.text_document.uri;
+        if let Some(document) = self.workspace.documents.get(&uri.to_string()) {
+            return Ok(check_document_symbols(document.value()).map(DocumentSymbolResponse::Nested));
+        }
+
+        Result::Err(tower_lsp::jsonrpc::Error::new(
+            tower_lsp::jsonrpc::ErrorCode::InternalError,
+        ))
+    }
+
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> Result<Option<Vec<SymbolInformation>>> {
+        Ok(check_workspace_symbols(
+            &self.workspace.documents,
+            &params.query,
+        ))
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        let uri = params.text_document.uri;
+        if let Some(document) = self.workspace.documents.get(&uri.to_string()) {
+            return Ok(check_prepare_rename(document.value(), params.position));
+        }
+
+        Result::Err(tower_lsp::jsonrpc::Error::new(
+            tower_lsp::jsonrpc::ErrorCode::InternalError,
+        ))
+    }
+
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let uri = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+
+        check_rename(
+            &self.workspace.documents,
+            uri.as_ref(),
+            position,
+            &params.new_name,
+        )
+        .map_err(tower_lsp::jsonrpc::Error::invalid_params)
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = params.text_document.uri;
+        if let Some(document) = self.workspace.documents.get(&uri.to_string()) {
+            return Ok(check_code_actions(
+                document.value(),
+                &params.context.diagnostics,
+            ));
+        }
+
+        Result::Err(tower_lsp::jsonrpc::Error::new(
+            tower_lsp::jsonrpc::ErrorCode::InternalError,
+        ))
+    }
+
+    async fn document_link(&self, params: DocumentLinkParams) -> Result<Option<Vec<DocumentLink>>> {
+        let uri = params.text_document.uri;
+        if let Some(document) = self.workspace.documents.get(&uri.to_string()) {
+            return Ok(check_document_links(document.value()));
+        }
+
+        Result::Err(tower_lsp::jsonrpc::Error::new(
+            tower_lsp::jsonrpc::ErrorCode::InternalError,
+        ))
+    }
+
+    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        let uri = params.text_document.uri;
+        if let Some(document) = self.workspace.documents.get(&uri.to_string()) {
+            return Ok(check_formatting(
+                document.value(),
+                &FormatterOptions::from_formatting_options(&params.options),
+            ));
+        }
+
+        Result::Err(tower_lsp::jsonrpc::Error::new(
+            tower_lsp::jsonrpc::ErrorCode::InternalError,
+        ))
+    }
+
+    async fn range_formatting(
+        &self,
+        params: DocumentRangeFormattingParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        let uri = params.text_document.uri;
+        if let Some(document) = self.workspace.documents.get(&uri.to_string()) {
+            return Ok(check_range_formatting(
+                document.value(),
+                params.range,
+                &FormatterOptions::from_formatting_options(&params.options),
+            ));
+        }
+
+        Result::Err(tower_lsp::jsonrpc::Error::new(
+            tower_lsp::jsonrpc::ErrorCode::InternalError,
+        ))
+    }
+
+    async fn semantic_tokens_full(
+        &self,
+        params: SemanticTokensParams,
+    ) -> Result<Option<SemanticTokensResult>> {
+        let uri = params.text_document.uri.to_string();
+        if let Some(document) = self.workspace.documents.get(&uri) {
+            let data = check_semantic_tokens(document.value());
+            return Ok(Some(SemanticTokensResult::Tokens(
+                self.store_semantic_tokens(uri, data),
+            )));
+        }
+
+        Result::Err(tower_lsp::jsonrpc::Error::new(
+            tower_lsp::jsonrpc::ErrorCode::InternalError,
+        ))
+    }
+
+    async fn semantic_tokens_full_delta(
+        &self,
+        params: SemanticTokensDeltaParams,
+    ) -> Result<Option<SemanticTokensFullDeltaResult>> {
+        let uri = params.text_document.uri.to_string();
+        let data = match self.workspace.documents.get(&uri) {
+            Some(document) => check_semantic_tokens(document.value()),
+            None => {
+                return Result::Err(tower_lsp::jsonrpc::Error::new(
+                    tower_lsp::jsonrpc::ErrorCode::InternalError,
+                ))
+            }
+        };
+
+        // We can only send a delta if the client still has the tokens we sent last
+        let previous = self
+            .semantic_tokens_map
+            .get(&uri)
+            .filter(|previous| previous.result_id.as_ref() == Some(&params.previous_result_id))
+            .map(|previous| previous.data.clone());
+
+        let tokens = self.store_semantic_tokens(uri, data);
+        match previous {
+            Some(previous) => Ok(Some(SemanticTokensFullDeltaResult::TokensDelta(
+                SemanticTokensDelta {
+                    result_id: tokens.result_id.clone(),
+                    edits: calculate_semantic_tokens_delta(&previous, &tokens.data),
+                },
+            ))),
+            None => Ok(Some(SemanticTokensFullDeltaResult::Tokens(tokens))),
+        }
+    }
 }
 
 #[tokio::main]
 async fn main() {
     env_logger::init();
 
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    if arguments.first().map(|argument| argument.as_str()) == Some("lint") {
+        std::process::exit(cli::run_lint(&arguments[1..]));
+    }
+
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
     let (service, socket) = LspService::build(|client| Backend {
         client: client.clone(),
-        document_map: DashMap::new(),
+        workspace: Workspace::default(),
+        semantic_tokens_map: DashMap::new(),
+        semantic_tokens_id: AtomicUsize::new(0),
+        workspace_folders: RwLock::new(Vec::new()),
     })
     .finish();
     Server::new(stdin, stdout, socket).serve(service).await;
@@ -0,0 +1,418 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use dashmap::DashMap;
+use serde::Deserialize;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Url};
+
+use crate::{
+    configuration::ServerConfiguration,
+    diagnostics::{diagnostic_codes::DiagnosticsCode, run_diagnostics, run_program_diagnostics},
+    document::DocumentData,
+    workspace::{get_included_documents, Program},
+};
+
+/**
+ * The name of the project file in the root of a workspace folder
+ */
+pub const PROJECT_FILE_NAME: &str = "asp-project.toml";
+
+/**
+ * The project file as it is written, see docs/project.md for the format
+ */
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ProjectFile {
+    groups: Vec<FileGroupEntry>,
+    constants: HashMap<String, toml::Value>,
+    lints: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct FileGroupEntry {
+    name: Option<String>,
+    files: Vec<String>,
+}
+
+/**
+ * A group of files that are grounded together as one program
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileGroup {
+    pub name: String,
+    pub files: Vec<Url>,
+}
+
+/**
+ * The configuration of a project described by an asp-project.toml file
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectConfiguration {
+    pub root: PathBuf,
+    pub groups: Vec<FileGroup>,
+    /**
+     * The default values of constants, like they would be given on the command line with -c
+     */
+    pub constants: HashMap<String, String>,
+    /**
+     * The severity of a lint by its code, None if the lint is turned off
+     */
+    pub severities: HashMap<i32, Option<DiagnosticSeverity>>,
+}
+
+impl ProjectConfiguration {
+    /**
+     * Read the project file in the folder, returns None if there is no project file
+     */
+    pub fn load(folder: &Path) -> Option<Result<ProjectConfiguration, String>> {
+        let path = folder.join(PROJECT_FILE_NAME);
+        if !path.is_file() {
+            return None;
+        }
+
+        Some(
+            fs::read_to_string(&path)
+                .map_err(|error| format!("Could not read {}: {}", path.display(), error))
+                .and_then(|source| ProjectConfiguration::parse(&source, folder)),
+        )
+    }
+
+    /**
+     * Parse the contents of a project file, the paths of the files are relative to the root
+     */
+    pub fn parse(source: &str, root: &Path) -> Result<ProjectConfiguration, String> {
+        let file: ProjectFile = toml::from_str(source)
+            .map_err(|error| format!("Invalid {}: {}", PROJECT_FILE_NAME, error))?;
+
+        let groups = file
+            .groups
+            .into_iter()
+            .enumerate()
+            .map(|(index, group)| FileGroup {
+                name: group.name.unwrap_or_else(|| format!("group {}", index + 1)),
+                files: group
+                    .files
+                    .iter()
+                    .filter_map(|file| Url::from_file_path(root.join(file)).ok())
+                    .collect(),
+            })
+            .collect();
+
+        let mut constants = HashMap::new();
+        for (name, value) in file.constants {
+            let value = match value {
+                toml::Value::String(value) => value,
+                toml::Value::Integer(value) => value.to_string(),
+                other => {
+                    return Err(format!(
+                        "Invalid {}: the value of constant '{}' must be a string or an integer, found {}",
+                        PROJECT_FILE_NAME,
+                        name,
+                        other.type_str()
+                    ))
+                }
+            };
+            constants.insert(name, value);
+        }
+
+        let mut severities = HashMap::new();
+        for (lint, severity) in file.lints {
+            let code = DiagnosticsCode::from_lint_name(&lint)
+                .ok_or_else(|| format!("Invalid {}: unknown lint '{}'", PROJECT_FILE_NAME, lint))?;
+            let severity = match severity.as_str() {
+                "off" => None,
+                "hint" => Some(DiagnosticSeverity::HINT),
+                "info" | "information" => Some(DiagnosticSeverity::INFORMATION),
+                "warning" => Some(DiagnosticSeverity::WARNING),
+                "error" => Some(DiagnosticSeverity::ERROR),
+                _ => {
+                    return Err(format!(
+                        "Invalid {}: unknown severity '{}' for lint '{}'",
+                        PROJECT_FILE_NAME, severity, lint
+                    ))
+                }
+            };
+            severities.insert(code.into_i32(), severity);
+        }
+
+        Ok(ProjectConfiguration {
+            root: root.to_path_buf(),
+            groups,
+            constants,
+            severities,
+        })
+    }
+
+    /**
+     * Get the groups the file is part of
+     */
+    pub fn get_groups_of(&self, uri: &Url) -> Vec<&FileGroup> {
+        self.groups
+            .iter()
+            .filter(|group| group.files.contains(uri))
+            .collect()
+    }
+
+    /**
+     * Add the constants of the project to the configuration, constants the client already sets are kept
+     */
+    pub fn extend_configuration(&self, configuration: &mut ServerConfiguration) {
+        for (name, value) in &self.constants {
+            configuration
+                .constants
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+    }
+
+    /**
+     * Change the severity of the diagnostics as the project configures, diagnostics of lints that are turned off are removed
+     */
+    pub fn apply_severities(&self, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        diagnostics
+            .into_iter()
+            .filter_map(|mut diagnostic| {
+                let code = match diagnostic.code {
+                    Some(NumberOrString::Number(code)) => code,
+                    _ => return Some(diagnostic),
+                };
+
+                match self.severities.get(&code) {
+                    Some(Some(severity)) => {
+                        diagnostic.severity = Some(*severity);
+                        Some(diagnostic)
+                    }
+                    Some(None) => None,
+                    None => Some(diagnostic),
+                }
+            })
+            .collect()
+    }
+
+    /**
+     * The programs of the groups a file is in, each group is a program of its files and the files they include.
+     * The files the file itself includes are part of every program
+     */
+    pub fn get_programs_of(
+        &self,
+        uri: &Url,
+        included: &[Url],
+        documents: &DashMap<String, DocumentData>,
+    ) -> Vec<Program> {
+        self.get_groups_of(uri)
+            .iter()
+            .map(|group| {
+                let mut program = group.get_program(documents);
+                if let Some(members) = &mut program.members {
+                    members.extend(included.iter().map(|uri| uri.to_string()));
+                }
+                program
+            })
+            .collect()
+    }
+
+    /**
+     * Run the diagnostics for a document of the project. If the project groups the document with other files, each group is a program of its files and the files they include.
+     * The documents have to be loaded already, running the diagnostics does not read any file
+     */
+    pub fn run_diagnostics(
+        &self,
+        document: DocumentData,
+        documents: &DashMap<String, DocumentData>,
+        maximum_number_of_problems: u32,
+    ) -> Vec<Diagnostic> {
+        // The document could have changed its includes since it was stored
+        let included = get_included_documents(&document, documents);
+        let programs = self.get_programs_of(&document.uri, &included, documents);
+
+        if programs.is_empty() {
+            return self.apply_severities(run_diagnostics(
+                document,
+                documents,
+                maximum_number_of_problems,
+            ));
+        }
+
+        self.apply_severities(run_program_diagnostics(
+            document,
+            documents,
+            &programs,
+            maximum_number_of_problems,
+        ))
+    }
+}
+
+impl FileGroup {
+    /**
+     * The program of the group, consisting of its files and the known files they include
+     */
+    pub fn get_program(&self, documents: &DashMap<String, DocumentData>) -> Program {
+        let mut members: HashSet<String> = HashSet::new();
+
+        for uri in &self.files {
+            members.insert(uri.to_string());
+            if let Some(member) = documents.get(uri.as_str()) {
+                members.extend(
+                    get_included_documents(member.value(), documents)
+                        .iter()
+                        .map(|included| included.to_string()),
+                );
+            }
+        }
+
+        Program {
+            members: Some(members),
+        }
+    }
+}
+
+#[cfg(test)]
+fn parse_test_project(source: &str) -> Result<ProjectConfiguration, String> {
+    ProjectConfiguration::parse(source, Path::new("/project"))
+}
+
+#[test]
+fn project_files_should_be_parsed() {
+    let project = parse_test_project(
+        r#"
+[[groups]]
+name = "small"
+files = ["instances/small.lp", "encoding.lp"]
+
+[[groups]]
+files = ["instances/large.lp", "encoding.lp"]
+
+[constants]
+n = 10
+mode = "fast"
+
+[lints]
+unused-predicate = "off"
+singleton-variable = "error"
+"#,
+    )
+    .unwrap();
+
+    let encoding = Url::from_file_path("/project/encoding.lp").unwrap();
+    let small = Url::from_file_path("/project/instances/small.lp").unwrap();
+
+    assert_eq!(project.groups.len(), 2);
+    assert_eq!(
+        project.groups[0].files,
+        vec![small.clone(), encoding.clone()]
+    );
+    assert_eq!(project.groups[1].name, "group 2");
+    assert_eq!(project.get_groups_of(&encoding).len(), 2);
+    assert_eq!(project.get_groups_of(&small).len(), 1);
+
+    assert_eq!(project.constants["n"], "10");
+    assert_eq!(project.constants["mode"], "fast");
+
+    assert_eq!(
+        project.severities[&DiagnosticsCode::UnusedPredicate.into_i32()],
+        None
+    );
+    assert_eq!(
+        project.severities[&DiagnosticsCode::SingletonVariable.into_i32()],
+        Some(DiagnosticSeverity::ERROR)
+    );
+}
+
+#[test]
+fn invalid_project_files_should_be_reported() {
+    assert!(parse_test_project("[lints]\nunknown-lint = \"off\"").is_err());
+    assert!(parse_test_project("[lints]\nsingleton-variable = \"loud\"").is_err());
+    assert!(parse_test_project("[constants]\nn = [1, 2]").is_err());
+    assert!(parse_test_project("[[groups]]\nfile = []").is_err());
+}
+
+#[test]
+fn project_severities_should_change_diagnostics() {
+    let project =
+        parse_test_project("[lints]\nunused-predicate = \"off\"\n2004 = \"hint\"").unwrap();
+    let create_diagnostic = |code: DiagnosticsCode| Diagnostic {
+        code: Some(NumberOrString::Number(code.into_i32())),
+        severity: Some(DiagnosticSeverity::WARNING),
+        ..Diagnostic::default()
+    };
+
+    let diagnostics = project.apply_severities(vec![
+        create_diagnostic(DiagnosticsCode::UnusedPredicate),
+        create_diagnostic(DiagnosticsCode::SingletonVariable),
+        create_diagnostic(DiagnosticsCode::UnsafeVariable),
+    ]);
+
+    let severities: Vec<Option<DiagnosticSeverity>> = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.severity)
+        .collect();
+    assert_eq!(
+        severities,
+        vec![
+            Some(DiagnosticSeverity::HINT),
+            Some(DiagnosticSeverity::WARNING)
+        ]
+    );
+}
+
+#[test]
+fn project_constants_should_not_replace_client_constants() {
+    let project = parse_test_project("[constants]\nn = 10\nm = 2").unwrap();
+    let mut configuration = ServerConfiguration::default();
+    configuration
+        .constants
+        .insert("n".to_string(), "5".to_string());
+
+    project.extend_configuration(&mut configuration);
+
+    assert_eq!(configuration.constants["n"], "5");
+    assert_eq!(configuration.constants["m"], "2");
+}
+
+#[test]
+fn problems_should_be_reported_if_they_occur_in_any_group() {
+    let project = parse_test_project(
+        r#"
+[[groups]]
+files = ["small.lp", "encoding.lp"]
+
+[[groups]]
+files = ["large.lp", "encoding.lp"]
+"#,
+    )
+    .unwrap();
+
+    let documents = DashMap::new();
+    for (file, source) in [
+        ("small.lp", "b."),
+        ("large.lp", "c."),
+        ("encoding.lp", "a :- b, c."),
+        ("other.lp", "b. c."),
+    ] {
+        let uri = Url::from_file_path(Path::new("/project").join(file)).unwrap();
+        documents.insert(uri.to_string(), DocumentData::parse(uri, source));
+    }
+
+    let encoding = documents
+        .get(
+            Url::from_file_path("/project/encoding.lp")
+                .unwrap()
+                .as_str(),
+        )
+        .unwrap()
+        .clone();
+    let diagnostics = project.run_diagnostics(encoding, &documents, 100);
+
+    // Each group misses one of the predicates, the file outside of the groups defines both
+    let messages: Vec<&str> = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect();
+    assert_eq!(messages.len(), 2);
+    assert!(messages[0].starts_with("'c/0' does not occur"));
+    assert!(messages[1].starts_with("'b/0' does not occur"));
+}
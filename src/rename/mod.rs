@@ -0,0 +1,82 @@
+use dashmap::DashMap;
+use tower_lsp::lsp_types::{Position, PrepareRenameResponse, WorkspaceEdit};
+
+use crate::document::DocumentData;
+
+use self::{
+    predicate_rename::{get_predicate_for_node, is_valid_identifier, rename_predicate},
+    variable_rename::{is_valid_variable, rename_variable},
+};
+
+pub mod predicate_rename;
+pub mod variable_rename;
+
+/**
+ * Check if the symbol at this position can be renamed and return its range
+ */
+pub fn check_prepare_rename(
+    document: &DocumentData,
+    position: Position,
+) -> Option<PrepareRenameResponse> {
+    let point = document.convert_position_to_point(position);
+    let node = document
+        .tree
+        .root_node()
+        .descendant_for_point_range(point, point)?;
+
+    if node.kind() != "VARIABLE" {
+        get_predicate_for_node(document, node)?;
+    }
+
+    Some(PrepareRenameResponse::Range(
+        document.convert_range(node.range()),
+    ))
+}
+
+/**
+ * Rename the symbol at this position, returns an error message if the rename is not allowed
+ */
+pub fn check_rename(
+    documents: &DashMap<String, DocumentData>,
+    uri: &str,
+    position: Position,
+    new_name: &str,
+) -> Result<Option<WorkspaceEdit>, String> {
+    let document = match documents.get(uri) {
+        Some(document) => document.clone(),
+        None => return Ok(None),
+    };
+
+    let point = document.convert_position_to_point(position);
+    let node = match document
+        .tree
+        .root_node()
+        .descendant_for_point_range(point, point)
+    {
+        Some(node) => node,
+        None => return Ok(None),
+    };
+
+    if node.kind() == "VARIABLE" {
+        if !is_valid_variable(new_name) {
+            return Err(format!("'{}' is not a valid variable name", new_name));
+        }
+
+        return rename_variable(&document, node, new_name).map(Some);
+    }
+
+    if let Some((identifier, arity)) = get_predicate_for_node(&document, node) {
+        if !is_valid_identifier(new_name) {
+            return Err(format!("'{}' is not a valid predicate name", new_name));
+        }
+
+        return Ok(Some(rename_predicate(
+            documents,
+            &identifier,
+            arity,
+            new_name,
+        )));
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use dashmap::DashMap;
+use tower_lsp::lsp_types::{Range, TextEdit, Url, WorkspaceEdit};
+use tree_sitter::{Node, Point};
+
+use crate::{document::DocumentData, semantics::predicate_semantics::PredicateSemantics};
+
+/**
+ * If the node is the identifier of a predicate (or of a signature like p/2) return the identifier and arity of the predicate
+ */
+pub fn get_predicate_for_node(document: &DocumentData, node: Node) -> Option<(String, usize)> {
+    if node.kind() != "identifier" {
+        return None;
+    }
+
+    let parent = node.parent()?;
+    match parent.kind() {
+        "atom" => {
+            if parent.child(0)? != node {
+                return None;
+            }
+
+            let mut arity = 0;
+            if parent.child_count() >= 3 {
+                arity = document
+                    .semantics
+                    .predicate_semantics
+                    .get_predicates_arity_for_node(&parent.child(2)?.id())
+                    + 1;
+            }
+
+            Some((document.get_source_for_range(node.range()), arity))
+        }
+        "statement" => {
+            let (identifier, arity, range) =
+                PredicateSemantics::get_signature_of_statement(&parent, document)?;
+
+            if range.start_byte != node.start_byte() {
+                return None;
+            }
+
+            Some((identifier, arity))
+        }
+        _ => None,
+    }
+}
+
+/**
+ * Create the edits needed to rename a predicate in every document, occurences with a different arity or terms with the same name are left untouched
+ */
+pub fn rename_predicate(
+    documents: &DashMap<String, DocumentData>,
+    identifier: &str,
+    arity: usize,
+    new_name: &str,
+) -> WorkspaceEdit {
+    let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+
+    for document in documents.iter() {
+        let occurences = match document
+            .semantics
+            .predicate_semantics
+            .predicates
+            .get(&(identifier.to_string(), arity))
+        {
+            Some(occurences) => occurences.clone(),
+            None => continue,
+        };
+
+        let mut edits: Vec<TextEdit> = occurences
+            .iter()
+            .filter(|occurence| occurence.is_atom)
+            .map(|occurence| {
+                // Every occurence starts with the identifier of the predicate
+                let start = occurence.range.start_point;
+                let end = Point {
+                    row: start.row,
+                    column: start.column + identifier.len(),
+                };
+
+                TextEdit::new(
+                    Range::new(
+                        document.convert_point_to_position(start),
+                        document.convert_point_to_position(end),
+                    ),
+                    new_name.to_string(),
+                )
+            })
+            .collect();
+
+        if edits.is_empty() {
+            continue;
+        }
+
+        edits.sort_by_key(|edit| edit.range.start);
+        changes.insert(document.uri.clone(), edits);
+    }
+
+    WorkspaceEdit::new(changes)
+}
+
+/**
+ * Check if a name can be used as the identifier of a predicate
+ */
+pub fn is_valid_identifier(name: &str) -> bool {
+    let name = name.trim_start_matches('_');
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) if first.is_ascii_lowercase() => {}
+        _ => return false,
+    }
+
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '\'')
+}
+
+#[cfg(test)]
+use crate::test_utils::create_test_document;
+
+#[cfg(test)]
+fn get_new_texts(source: &str, identifier: &str, arity: usize, new_name: &str) -> Vec<String> {
+    let documents = DashMap::new();
+    documents.insert(
+        "file://test.lp".to_string(),
+        create_test_document(source.to_string()),
+    );
+
+    let edit = rename_predicate(&documents, identifier, arity, new_name);
+    let mut texts = Vec::new();
+
+    for (_, edits) in edit.changes.unwrap() {
+        for edit in edits {
+            texts.push(format!(
+                "{}:{}",
+                edit.range.start.character, edit.range.end.character
+            ));
+        }
+    }
+
+    texts
+}
+
+#[test]
+fn rename_should_only_change_predicates_with_same_arity() {
+    let texts = get_new_texts("p(1,2). p(1,2,3). a :- p(X,Y).", "p", 2, "q");
+
+    assert_eq!(texts, vec!["0:1", "23:24"]);
+}
+
+#[test]
+fn rename_should_change_show_signatures() {
+    let texts = get_new_texts("p(1,2). #show p/2. #show p/3.", "p", 2, "q");
+
+    assert_eq!(texts, vec!["0:1", "14:15"]);
+}
+
+#[test]
+fn rename_should_not_change_terms_with_the_same_name() {
+    let texts = get_new_texts("b. a :- b, c(b).", "b", 0, "d");
+
+    assert_eq!(texts, vec!["0:1", "8:9"]);
+}
+
+#[test]
+fn identifiers_should_be_validated() {
+    assert!(is_valid_identifier("edge"));
+    assert!(is_valid_identifier("_edge'"));
+    assert!(!is_valid_identifier("Edge"));
+    assert!(!is_valid_identifier("ed ge"));
+    assert!(!is_valid_identifier(""));
+}
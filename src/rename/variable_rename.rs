@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use tower_lsp::lsp_types::{TextEdit, WorkspaceEdit};
+use tree_sitter::Node;
+
+use crate::{
+    diagnostics::{
+        statement_analysis::get_variables_in_statement, variable_analysis::get_scope_of_variable,
+    },
+    document::DocumentData,
+};
+
+/**
+ * Rename every occurence of a variable in its scope, returns an error if the new name is already used in that scope
+ */
+pub fn rename_variable(
+    document: &DocumentData,
+    node: Node,
+    new_name: &str,
+) -> Result<WorkspaceEdit, String> {
+    let scope = match get_scope_of_variable(document, node) {
+        Some(scope) => scope,
+        None => return Ok(WorkspaceEdit::default()),
+    };
+
+    let variable = document.get_source_for_range(node.range());
+    let source = document.get_bytes();
+    let statement_semantics = document
+        .semantics
+        .get_statement_semantics_for_node(scope.statement.id());
+
+    let mut edits = Vec::new();
+    for (range, name, _) in get_variables_in_statement(&scope.statement, &source) {
+        let in_scope = range.start_byte >= scope.start_byte && range.end_byte <= scope.end_byte;
+
+        // Renaming would capture a variable that is already visible in this scope, variables in other local contexts are different variables
+        if name == new_name && (in_scope || statement_semantics.global_vars.contains(name)) {
+            return Err(format!(
+                "'{}' is already used in this scope, renaming '{}' would capture it",
+                new_name, variable
+            ));
+        }
+
+        if in_scope && name == variable {
+            edits.push(TextEdit::new(
+                document.convert_range(range),
+                new_name.to_string(),
+            ));
+        }
+    }
+
+    let mut changes = HashMap::new();
+    changes.insert(document.uri.clone(), edits);
+    Ok(WorkspaceEdit::new(changes))
+}
+
+/**
+ * Check if a name can be used as a variable
+ */
+pub fn is_valid_variable(name: &str) -> bool {
+    let name = name.trim_start_matches('_');
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) if first.is_ascii_uppercase() => {}
+        _ => return false,
+    }
+
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '\'')
+}
+
+#[cfg(test)]
+use crate::test_utils::create_test_document;
+
+#[cfg(test)]
+fn get_renamed_ranges(source: &str, byte: usize, new_name: &str) -> Result<Vec<String>, String> {
+    let doc = create_test_document(source.to_string());
+    let node = doc
+        .tree
+        .root_node()
+        .descendant_for_byte_range(byte, byte)
+        .unwrap();
+
+    let edit = rename_variable(&doc, node, new_name)?;
+    let mut ranges: Vec<String> = edit
+        .changes
+        .unwrap()
+        .values()
+        .flatten()
+        .map(|edit| format!("{}", edit.range.start.character))
+        .collect();
+    ranges.sort();
+
+    Ok(ranges)
+}
+
+#[test]
+fn global_variables_should_be_renamed_in_the_whole_statement() {
+    let ranges = get_renamed_ranges("a(X) :- b(X), c(X). d(X).", 2, "Y").unwrap();
+
+    assert_eq!(ranges, vec!["10", "16", "2"]);
+}
+
+#[test]
+fn local_variables_should_only_be_renamed_in_their_aggregate_element() {
+    let ranges = get_renamed_ranges("a :- #count{X : b(X); X : c(X)} > 1.", 12, "Y").unwrap();
+
+    assert_eq!(ranges, vec!["12", "18"]);
+}
+
+#[test]
+fn local_variables_should_only_be_renamed_in_their_conditional_literal() {
+    let ranges = get_renamed_ranges("a :- c(X) : d(X); e(X) : f(X).", 7, "Y").unwrap();
+
+    assert_eq!(ranges, vec!["14", "7"]);
+}
+
+#[test]
+fn rename_should_be_rejected_if_it_captures_a_variable() {
+    assert!(get_renamed_ranges("a(X) :- b(X,Y).", 2, "Y").is_err());
+    assert!(get_renamed_ranges("a(Y) :- #count{X : b(X)} > Y.", 15, "Y").is_err());
+}
+
+#[test]
+fn variable_names_should_be_validated() {
+    assert!(is_valid_variable("X"));
+    assert!(is_valid_variable("_Xy'"));
+    assert!(!is_valid_variable("x"));
+    assert!(!is_valid_variable("_"));
+}
@@ -0,0 +1,322 @@
+use std::collections::{HashMap, HashSet};
+
+use tower_lsp::lsp_types::{
+    SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokensEdit,
+    SemanticTokensLegend,
+};
+use tree_sitter::Node;
+
+use crate::{
+    diagnostics::statement_analysis::get_unsafe_variables_of_statement, document::DocumentData,
+    semantics::predicate_occurence_semantics::PredicateOccurenceLocation,
+};
+
+/**
+ * The token types we use, the index in this list is the id of the token type
+ */
+pub const TOKEN_TYPES: [SemanticTokenType; 8] = [
+    SemanticTokenType::FUNCTION, // Predicate in the head
+    SemanticTokenType::METHOD,   // Predicate in the body
+    SemanticTokenType::PROPERTY, // Predicate in a condition
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::MACRO, // Constants declared with #const
+    SemanticTokenType::NUMBER,
+    SemanticTokenType::STRING,
+    SemanticTokenType::KEYWORD,
+];
+
+const HEAD_PREDICATE: u32 = 0;
+const BODY_PREDICATE: u32 = 1;
+const CONDITION_PREDICATE: u32 = 2;
+const VARIABLE: u32 = 3;
+const CONSTANT: u32 = 4;
+const NUMBER: u32 = 5;
+const STRING: u32 = 6;
+const KEYWORD: u32 = 7;
+
+/**
+ * The token modifiers we use, the index in this list is the bit of the modifier
+ */
+pub fn get_token_modifiers() -> Vec<SemanticTokenModifier> {
+    vec![
+        SemanticTokenModifier::DECLARATION,
+        SemanticTokenModifier::new("unsafe"),
+    ]
+}
+
+const DECLARATION_MODIFIER: u32 = 1;
+const UNSAFE_MODIFIER: u32 = 1 << 1;
+
+/**
+ * Nodes which are directives or keywords
+ */
+const KEYWORDS: [&str; 23] = [
+    "SHOW",
+    "CONST",
+    "BLOCK",
+    "INCLUDE",
+    "EXTERNAL",
+    "DEFINED",
+    "MINIMIZE",
+    "MAXIMIZE",
+    "HEURISTIC",
+    "PROJECT",
+    "EDGE",
+    "SCRIPT",
+    "THEORY",
+    "NOT",
+    "COUNT",
+    "SUM",
+    "SUMP",
+    "MIN",
+    "MAX",
+    "TRUE",
+    "FALSE",
+    "DEFAULT",
+    "OVERRIDE",
+];
+
+/**
+ * The legend the client needs to decode our tokens
+ */
+pub fn get_semantic_tokens_legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: get_token_modifiers(),
+    }
+}
+
+/**
+ * Create the semantic tokens for the entire document
+ */
+pub fn check_semantic_tokens(document: &DocumentData) -> Vec<SemanticToken> {
+    // Find out where each predicate occurs, so we can give it the correct token type
+    let mut predicate_locations: HashMap<usize, PredicateOccurenceLocation> = HashMap::new();
+    for predicate in document.semantics.predicate_semantics.predicates.iter() {
+        for occurence in predicate.value() {
+            if occurence.is_atom {
+                predicate_locations.insert(occurence.node_id, occurence.location.clone());
+            }
+        }
+    }
+
+    // Collect the tokens in the order they occur in the document
+    let mut tokens: Vec<(tree_sitter::Range, u32, u32)> = Vec::new();
+    let mut unsafe_vars: HashSet<String> = HashSet::new();
+    let mut cursor = document.tree.walk();
+    let mut stack = vec![document.tree.root_node()];
+
+    while let Some(node) = stack.pop() {
+        let mut visit_children = true;
+
+        match node.kind() {
+            "statement" => {
+                unsafe_vars = get_unsafe_variables_of_statement(&node, document);
+            }
+            "identifier" => {
+                if let Some(token) = get_identifier_token(document, node, &predicate_locations) {
+                    tokens.push((node.range(), token.0, token.1));
+                }
+            }
+            "VARIABLE" => {
+                let mut modifiers = 0;
+                if unsafe_vars.contains(&document.get_source_for_range(node.range())) {
+                    modifiers |= UNSAFE_MODIFIER;
+                }
+                tokens.push((node.range(), VARIABLE, modifiers));
+            }
+            "NUMBER" => {
+                tokens.push((node.range(), NUMBER, 0));
+                visit_children = false;
+            }
+            "STRING" => {
+                tokens.push((node.range(), STRING, 0));
+                visit_children = false;
+            }
+            kind if KEYWORDS.contains(&kind) => {
+                tokens.push((node.range(), KEYWORD, 0));
+                visit_children = false;
+            }
+            _ => {}
+        }
+
+        if visit_children {
+            let children: Vec<Node> = node.children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev());
+        }
+    }
+
+    encode_tokens(document, tokens)
+}
+
+/**
+ * Calculate the edits needed to go from the previous tokens to the current tokens
+ */
+pub fn calculate_semantic_tokens_delta(
+    previous: &[SemanticToken],
+    current: &[SemanticToken],
+) -> Vec<SemanticTokensEdit> {
+    let prefix = previous
+        .iter()
+        .zip(current.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let suffix = previous[prefix..]
+        .iter()
+        .rev()
+        .zip(current[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let deleted = previous.len() - prefix - suffix;
+    let inserted = &current[prefix..current.len() - suffix];
+
+    if deleted == 0 && inserted.is_empty() {
+        return Vec::new();
+    }
+
+    // Each token consists out of 5 integers, the edits work on these integers
+    vec![SemanticTokensEdit {
+        start: (prefix * 5) as u32,
+        delete_count: (deleted * 5) as u32,
+        data: Some(inserted.to_vec()),
+    }]
+}
+
+/**
+ * Find the token type and modifiers of an identifier
+ */
+fn get_identifier_token(
+    document: &DocumentData,
+    node: Node,
+    predicate_locations: &HashMap<usize, PredicateOccurenceLocation>,
+) -> Option<(u32, u32)> {
+    let parent = node.parent()?;
+
+    match parent.kind() {
+        "atom" => {
+            let location = predicate_locations.get(&parent.id())?;
+            Some(match location {
+                PredicateOccurenceLocation::Head => (HEAD_PREDICATE, DECLARATION_MODIFIER),
+                PredicateOccurenceLocation::Body => (BODY_PREDICATE, 0),
+                PredicateOccurenceLocation::Condition => (CONDITION_PREDICATE, 0),
+                _ => (HEAD_PREDICATE, 0),
+            })
+        }
+        "statement" => match parent.child(0)?.kind() {
+            "CONST" => Some((CONSTANT, DECLARATION_MODIFIER)),
+            "SHOW" | "DEFINED" => Some((HEAD_PREDICATE, 0)),
+            _ => None,
+        },
+        "term" | "constterm" => document
+            .semantics
+            .constant_semantics
+            .get_constant_of_node(node, document)
+            .map(|_| (CONSTANT, 0)),
+        _ => None,
+    }
+}
+
+/**
+ * Sort the tokens and encode them relative to each other as the protocol requires
+ */
+fn encode_tokens(
+    document: &DocumentData,
+    mut tokens: Vec<(tree_sitter::Range, u32, u32)>,
+) -> Vec<SemanticToken> {
+    tokens.sort_by_key(|(range, _, _)| range.start_byte);
+
+    let mut encoded = Vec::with_capacity(tokens.len());
+    let mut previous_line = 0;
+    let mut previous_start = 0;
+
+    for (range, token_type, modifiers) in tokens {
+        // The columns and lengths are counted in the position encoding of the client
+        let range = document.convert_range(range);
+
+        // Tokens can not span multiple lines
+        if range.start.line != range.end.line {
+            continue;
+        }
+        let line = range.start.line;
+        let start = range.start.character;
+
+        let delta_line = line - previous_line;
+        let delta_start = if delta_line == 0 {
+            start - previous_start
+        } else {
+            start
+        };
+
+        encoded.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length: range.end.character - start,
+            token_type,
+            token_modifiers_bitset: modifiers,
+        });
+
+        previous_line = line;
+        previous_start = start;
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+use crate::test_utils::create_test_document;
+
+#[test]
+fn predicates_should_get_token_type_based_on_location() {
+    let doc = create_test_document("a(X) :- b(X).".to_string());
+    let tokens = check_semantic_tokens(&doc);
+
+    let types: Vec<u32> = tokens.iter().map(|token| token.token_type).collect();
+    assert_eq!(
+        types,
+        vec![HEAD_PREDICATE, VARIABLE, BODY_PREDICATE, VARIABLE]
+    );
+}
+
+#[test]
+fn unsafe_variables_should_get_unsafe_modifier() {
+    let doc = create_test_document("a(X) :- not b(X).".to_string());
+    let tokens = check_semantic_tokens(&doc);
+
+    assert_eq!(tokens[1].token_type, VARIABLE);
+    assert_eq!(tokens[1].token_modifiers_bitset, UNSAFE_MODIFIER);
+}
+
+#[test]
+fn constants_should_get_their_own_token_type() {
+    let doc = create_test_document("#const n=3. a(1..n).".to_string());
+    let tokens = check_semantic_tokens(&doc);
+
+    let types: Vec<u32> = tokens.iter().map(|token| token.token_type).collect();
+    assert_eq!(
+        types,
+        vec![KEYWORD, CONSTANT, NUMBER, HEAD_PREDICATE, NUMBER, CONSTANT]
+    );
+
+    let doc = create_test_document("#const n=3. [override]".to_string());
+    let tokens = check_semantic_tokens(&doc);
+
+    let types: Vec<u32> = tokens.iter().map(|token| token.token_type).collect();
+    assert_eq!(types, vec![KEYWORD, CONSTANT, NUMBER, KEYWORD]);
+}
+
+#[test]
+fn delta_should_only_contain_changed_tokens() {
+    let previous = check_semantic_tokens(&create_test_document("a.\nb.\nc.".to_string()));
+    let current = check_semantic_tokens(&create_test_document("a.\nd(X) :- e(X).\nc.".to_string()));
+
+    let edits = calculate_semantic_tokens_delta(&previous, &current);
+
+    // The tokens of b and d are encoded the same way, only the tokens after d are new
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0].start, 10);
+    assert_eq!(edits[0].delete_count, 0);
+    assert_eq!(edits[0].data.as_ref().unwrap().len(), 3);
+    assert!(calculate_semantic_tokens_delta(&current, &current).is_empty());
+}
@@ -0,0 +1,494 @@
+use std::collections::{HashMap, HashSet};
+
+use dashmap::DashMap;
+use tower_lsp::lsp_types::Url;
+use tree_sitter::Node;
+
+use crate::document::DocumentData;
+
+use super::{
+    encoding_semantic::{EncodingSemantics, Semantics},
+    term_semantic::{OperationChain, TermOperator, TermSemantic, TermType},
+};
+
+/**
+ * Constant Semantics infers information about the constants declared with #const, where they are used and what value they have
+ */
+#[derive(Clone, Debug)]
+pub struct ConstantSemantics {
+    pub constants: DashMap<String, HashSet<ConstantOccurenceSemantics>>,
+    /**
+     * The values of the constants that evaluate to numbers
+     */
+    pub values: DashMap<String, HashSet<i64>>,
+    /**
+     * The constants that are declared in terms of themselves, directly or through other constants
+     */
+    pub cyclic: HashSet<String>,
+}
+
+/**
+ * Constant Occurence Semantics infers information where a constant occured
+ */
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ConstantOccurenceSemantics {
+    pub node_id: usize,
+    /**
+     * The range of the identifier of the constant
+     */
+    pub range: tree_sitter::Range,
+    /**
+     * If this occurence is the #const statement declaring the constant, otherwise it is a term using the constant
+     */
+    pub is_declaration: bool,
+}
+
+/**
+ * A constant declared with #const in another document of the program
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExternalConstant {
+    pub uri: Url,
+    /**
+     * The range of the identifier in the declaring document
+     */
+    pub range: tree_sitter::Range,
+    /**
+     * The source of the #const statement
+     */
+    pub declaration: String,
+    pub value: Option<HashSet<i64>>,
+}
+
+impl ConstantSemantics {
+    pub fn new() -> ConstantSemantics {
+        ConstantSemantics {
+            constants: DashMap::new(),
+            values: DashMap::new(),
+            cyclic: HashSet::new(),
+        }
+    }
+
+    /**
+     * Add an occurence of a constant, if there are no occurences for that constant yet it creates them
+     */
+    pub fn insert_constant_for_node(
+        semantics: &EncodingSemantics,
+        identifier: String,
+        new_value: ConstantOccurenceSemantics,
+    ) {
+        semantics
+            .constant_semantics
+            .constants
+            .entry(identifier)
+            .or_default()
+            .insert(new_value);
+    }
+
+    /**
+     * Get the declarations of a constant, sorted by their position in the document
+     */
+    pub fn get_declarations(&self, identifier: &str) -> Vec<ConstantOccurenceSemantics> {
+        let mut declarations: Vec<ConstantOccurenceSemantics> = match self.constants.get(identifier)
+        {
+            Some(occurences) => occurences
+                .iter()
+                .filter(|occurence| occurence.is_declaration)
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        declarations.sort_by_key(|declaration| declaration.range.start_byte);
+
+        declarations
+    }
+
+    /**
+     * If the node is an identifier that refers to a declared constant return the name of the constant
+     */
+    pub fn get_constant_of_node(&self, node: Node, document: &DocumentData) -> Option<String> {
+        if node.kind() != "identifier" {
+            return None;
+        }
+
+        let parent = node.parent()?;
+        let is_constant = match parent.kind() {
+            "statement" => parent.child(0)?.kind() == "CONST",
+            "term" | "constterm" => parent.child_count() == 1,
+            _ => false,
+        };
+
+        let identifier = document.get_source_for_range(node.range());
+        if is_constant
+            && (self.constants.contains_key(&identifier)
+                || document
+                    .semantics
+                    .external_constants
+                    .contains_key(&identifier))
+        {
+            return Some(identifier);
+        }
+
+        None
+    }
+
+    /**
+     * The constants the document declares, as they are seen by the other documents of the program
+     */
+    pub fn get_exported_constants(document: &DocumentData) -> HashMap<String, ExternalConstant> {
+        let constants = &document.semantics.constant_semantics;
+        let mut exported = HashMap::new();
+
+        for (identifier, statement) in Self::get_declaration_statements(document) {
+            if exported.contains_key(&identifier) {
+                continue;
+            }
+
+            let value = constants
+                .values
+                .get(&identifier)
+                .map(|value| value.value().clone());
+            exported.insert(
+                identifier,
+                ExternalConstant {
+                    uri: document.uri.clone(),
+                    range: statement.child(1).unwrap().range(),
+                    declaration: document.get_source_for_range(statement.range()),
+                    value,
+                },
+            );
+        }
+
+        exported
+    }
+
+    /**
+     * Find all #const statements in the document
+     */
+    fn get_declaration_statements(document: &DocumentData) -> Vec<(String, Node<'_>)> {
+        let root = document.tree.root_node();
+
+        root.children(&mut root.walk())
+            .filter(|statement| {
+                statement.kind() == "statement"
+                    && statement.child(0).map(|child| child.kind()) == Some("CONST")
+                    && statement.child(1).map(|child| child.kind()) == Some("identifier")
+            })
+            .map(|statement| {
+                (
+                    document.get_source_for_range(statement.child(1).unwrap().range()),
+                    statement,
+                )
+            })
+            .collect()
+    }
+
+    /**
+     * Check if a #const statement is marked with [override], then it is not replaced by a value given on the command line
+     */
+    fn is_override(statement: Node) -> bool {
+        let mut cursor = statement.walk();
+        let is_override = statement
+            .children(&mut cursor)
+            .any(|child| child.kind() == "OVERRIDE");
+        is_override
+    }
+
+    /**
+     * The constants the declaration is evaluated with, a constant replaced by a value given on the command line does not depend on others
+     */
+    fn get_dependencies(statement: Node, document: &DocumentData) -> Vec<String> {
+        let identifier = document.get_source_for_range(statement.child(1).unwrap().range());
+        if document
+            .semantics
+            .constant_overrides
+            .contains_key(&identifier)
+            && !Self::is_override(statement)
+        {
+            return Vec::new();
+        }
+
+        let mut dependencies = Vec::new();
+        let mut stack: Vec<Node> = statement.child(3).into_iter().collect();
+        while let Some(node) = stack.pop() {
+            if node.kind() == "identifier"
+                && node
+                    .parent()
+                    .is_some_and(|parent| parent.kind() == "constterm" && parent.child_count() == 1)
+            {
+                dependencies.push(document.get_source_for_range(node.range()));
+            }
+
+            stack.extend(node.children(&mut node.walk()));
+        }
+
+        dependencies
+    }
+
+    /**
+     * Find the constants that depend on themselves through the declarations they are evaluated with
+     */
+    fn get_cyclic_constants(
+        document: &DocumentData,
+        declarations: &HashMap<String, Node>,
+    ) -> HashSet<String> {
+        let dependencies: HashMap<&String, Vec<String>> = declarations
+            .iter()
+            .map(|(identifier, statement)| {
+                (identifier, Self::get_dependencies(*statement, document))
+            })
+            .collect();
+
+        let mut cyclic = HashSet::new();
+        for identifier in declarations.keys() {
+            let mut visited: HashSet<&String> = HashSet::new();
+            let mut stack: Vec<&String> = dependencies[identifier].iter().collect();
+            while let Some(current) = stack.pop() {
+                if current == identifier {
+                    cyclic.insert(identifier.clone());
+                    break;
+                }
+                if !visited.insert(current) {
+                    continue;
+                }
+                if let Some(next) = dependencies.get(current) {
+                    stack.extend(next.iter());
+                }
+            }
+        }
+
+        cyclic
+    }
+
+    /**
+     * Evaluate a term of a #const statement, constants that are used in it are resolved with their first declaration
+     */
+    pub fn evaluate_constant_term(node: Node, document: &DocumentData) -> Option<HashSet<i64>> {
+        let mut declarations: HashMap<String, Node> = HashMap::new();
+        for (identifier, statement) in Self::get_declaration_statements(document) {
+            declarations.entry(identifier).or_insert(statement);
+        }
+
+        Self::evaluate_constterm(node, document, &declarations, &mut HashSet::new())
+    }
+
+    /**
+     * Evaluate the value of a constant term, constants that are used in it are resolved with their first declaration
+     */
+    fn evaluate_constterm(
+        node: Node,
+        document: &DocumentData,
+        declarations: &HashMap<String, Node>,
+        visiting: &mut HashSet<String>,
+    ) -> Option<HashSet<i64>> {
+        match node.kind() {
+            "NUMBER" => {
+                let number =
+                    TermSemantic::parse_number(&document.get_source_for_range(node.range()))?;
+                Some(HashSet::from([number]))
+            }
+            "identifier" => {
+                let identifier = document.get_source_for_range(node.range());
+                let declaration = declarations.get(&identifier);
+
+                // A value given on the command line replaces the declaration, unless it is marked with [override]
+                if let Some(value) = document.semantics.constant_overrides.get(&identifier) {
+                    if !declaration.is_some_and(|declaration| Self::is_override(*declaration)) {
+                        return TermSemantic::parse_number(value)
+                            .map(|number| HashSet::from([number]));
+                    }
+                }
+
+                // A constant that is not declared in the document can be declared in another document of the program
+                let declaration = match declaration {
+                    Some(declaration) => declaration,
+                    None => {
+                        return document
+                            .semantics
+                            .external_constants
+                            .get(&identifier)
+                            .and_then(|constant| constant.value.clone())
+                    }
+                };
+
+                // A constant that depends on itself has no value
+                if !visiting.insert(identifier.clone()) {
+                    return None;
+                }
+                let value = Self::evaluate_constterm(
+                    declaration.child(3)?,
+                    document,
+                    declarations,
+                    visiting,
+                );
+                visiting.remove(&identifier);
+
+                value
+            }
+            "constterm" => {
+                if let Some(chain) = OperationChain::from_node(node) {
+                    let operands: Vec<TermSemantic> = chain
+                        .operands
+                        .iter()
+                        .map(|operand| {
+                            match Self::evaluate_constterm(
+                                operand.term,
+                                document,
+                                declarations,
+                                visiting,
+                            ) {
+                                Some(value) => TermSemantic {
+                                    kind: TermType::Constant,
+                                    value,
+                                    ..TermSemantic::new()
+                                },
+                                None => TermSemantic::new(),
+                            }
+                        })
+                        .collect();
+
+                    let term = chain.evaluate(
+                        &operands,
+                        document.semantics.interval_limit,
+                        &mut |_, _, _| {},
+                    );
+                    if term.kind != TermType::Constant || term.exceeds_limit {
+                        return None;
+                    }
+                    return Some(term.value);
+                }
+
+                match node.child_count() {
+                    1 => Self::evaluate_constterm(node.child(0)?, document, declarations, visiting),
+                    3 => {
+                        let inner = node.child(1)?;
+                        match node.child(0)?.kind() {
+                            "VBAR" => {
+                                let child = TermSemantic {
+                                    value: Self::evaluate_constterm(
+                                        inner,
+                                        document,
+                                        declarations,
+                                        visiting,
+                                    )?,
+                                    ..TermSemantic::new()
+                                };
+
+                                Some(TermSemantic::evaluate_unary(&child, &TermOperator::Abs))
+                            }
+                            // Parentheses around a single term, tuples have no value
+                            "LPAREN" if inner.child_count() == 1 => Self::evaluate_constterm(
+                                inner.child(0)?,
+                                document,
+                                declarations,
+                                visiting,
+                            ),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Default for ConstantSemantics {
+    fn default() -> Self {
+        ConstantSemantics::new()
+    }
+}
+
+impl Semantics for ConstantSemantics {
+    fn on_node(node: Node, document: &mut DocumentData) {
+        // The declarations are already known, so only the uses have to be found
+        if node.kind() == "identifier" {
+            if let Some(identifier) = document
+                .semantics
+                .constant_semantics
+                .get_constant_of_node(node, document)
+            {
+                if node.parent().unwrap().kind() != "statement" {
+                    Self::insert_constant_for_node(
+                        &document.semantics,
+                        identifier,
+                        ConstantOccurenceSemantics {
+                            node_id: node.id(),
+                            range: node.range(),
+                            is_declaration: false,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    /**
+     * Constants can be used before they are declared, so the declarations and their values are collected before the analysis
+     */
+    fn startup(document: &mut DocumentData) {
+        let statements = Self::get_declaration_statements(document);
+
+        let mut declarations: HashMap<String, Node> = HashMap::new();
+        let constants: DashMap<String, HashSet<ConstantOccurenceSemantics>> = DashMap::new();
+        for (identifier, statement) in &statements {
+            declarations.entry(identifier.clone()).or_insert(*statement);
+            constants
+                .entry(identifier.clone())
+                .or_default()
+                .insert(ConstantOccurenceSemantics {
+                    node_id: statement.id(),
+                    range: statement.child(1).unwrap().range(),
+                    is_declaration: true,
+                });
+        }
+
+        let values: DashMap<String, HashSet<i64>> = DashMap::new();
+        for (identifier, statement) in &declarations {
+            if let Some(value) = Self::evaluate_constterm(
+                statement.child(1).unwrap(),
+                document,
+                &declarations,
+                &mut HashSet::new(),
+            ) {
+                values.insert(identifier.clone(), value);
+            }
+        }
+
+        // Constants given on the command line can be used without a declaration
+        for (identifier, value) in &document.semantics.constant_overrides {
+            if declarations.contains_key(identifier) {
+                continue;
+            }
+            if let Some(number) = TermSemantic::parse_number(value) {
+                values.insert(identifier.clone(), HashSet::from([number]));
+            }
+        }
+
+        // The declarations of the other documents come after the command line
+        for (identifier, constant) in &document.semantics.external_constants {
+            if declarations.contains_key(identifier) || values.contains_key(identifier) {
+                continue;
+            }
+            if let Some(value) = &constant.value {
+                values.insert(identifier.clone(), value.clone());
+            }
+        }
+
+        // The terms using a constant have to be evaluated again when the value changes
+        let old_values = &document.semantics.constant_semantics.values;
+        let changed = old_values.len() != values.len()
+            || values
+                .iter()
+                .any(|value| old_values.get(value.key()).as_deref() != Some(value.value()));
+        if changed {
+            document.semantics.statement_semantics.clear();
+        }
+
+        document.semantics.constant_semantics.cyclic =
+            Self::get_cyclic_constants(document, &declarations);
+        document.semantics.constant_semantics.constants = constants;
+        document.semantics.constant_semantics.values = values;
+    }
+}
@@ -1,12 +1,21 @@
 use super::{
-    predicate_semantics::PredicateSemantics, statement_semantic::StatementSemantics,
-    syntax::Syntax, term_semantic::TermSemantic,
+    constant_semantics::{ConstantSemantics, ExternalConstant},
+    predicate_semantics::PredicateSemantics,
+    statement_semantic::StatementSemantics,
+    syntax::Syntax,
+    term_semantic::TermSemantic,
 };
 use crate::document::DocumentData;
 use dashmap::{DashMap, DashSet};
 use rust_lapper::Lapper;
+use std::collections::HashMap;
 use tree_sitter::Node;
 
+/**
+ * The number of values an interval or pool is expanded to before we give up on evaluating it
+ */
+pub const DEFAULT_INTERVAL_LIMIT: usize = 1000;
+
 /**
  * Encoding semantics are all the information needed about the program that then can be used by the other parts of the LSP
  */
@@ -14,9 +23,19 @@ use tree_sitter::Node;
 pub struct EncodingSemantics {
     pub syntax: Syntax,
     pub predicate_semantics: PredicateSemantics,
+    pub constant_semantics: ConstantSemantics,
     pub statement_semantics: DashMap<usize, StatementSemantics>,
     pub old_node_ids_encountered: DashSet<usize>,
     pub node_ids_encountered: DashSet<usize>,
+    pub interval_limit: usize,
+    /**
+     * Constants given on the command line with -c, they replace the values of #const statements
+     */
+    pub constant_overrides: HashMap<String, String>,
+    /**
+     * Constants declared in the other documents of the program, they are used if the document does not declare them itself
+     */
+    pub external_constants: HashMap<String, ExternalConstant>,
 }
 
 impl EncodingSemantics {
@@ -24,12 +43,28 @@ impl EncodingSemantics {
         EncodingSemantics {
             syntax: Syntax::new(),
             predicate_semantics: PredicateSemantics::new(),
+            constant_semantics: ConstantSemantics::new(),
             statement_semantics: DashMap::new(),
             old_node_ids_encountered: DashSet::new(),
             node_ids_encountered: DashSet::new(),
+            interval_limit: DEFAULT_INTERVAL_LIMIT,
+            constant_overrides: HashMap::new(),
+            external_constants: HashMap::new(),
         }
     }
 
+    /**
+     * Forget everything that was inferred from the previous trees, the configuration and the other documents the encoding is analyzed with are kept
+     */
+    pub fn reset(&mut self) {
+        let mut semantics = EncodingSemantics::new();
+        semantics.interval_limit = self.interval_limit;
+        semantics.constant_overrides = std::mem::take(&mut self.constant_overrides);
+        semantics.external_constants = std::mem::take(&mut self.external_constants);
+
+        *self = semantics;
+    }
+
     /**
      * This can be used if any cleanup of previous iterations needs to be done to the document and is called just before analysis starts
      */
@@ -40,6 +75,7 @@ impl EncodingSemantics {
 
         Syntax::startup(document);
         PredicateSemantics::startup(document);
+        ConstantSemantics::startup(document);
     }
 
     /**
@@ -95,6 +131,7 @@ impl EncodingSemantics {
      */
     fn checks_that_always_need_to_happen(node: Node, document: &mut DocumentData) {
         PredicateSemantics::on_node(node, document);
+        ConstantSemantics::on_node(node, document);
         Syntax::on_node(node, document);
     }
 
@@ -135,6 +172,12 @@ impl EncodingSemantics {
     }
 }
 
+impl Default for EncodingSemantics {
+    fn default() -> Self {
+        EncodingSemantics::new()
+    }
+}
+
 /**
  * Each of the semantic analyzers need to implement the on_node function that will be called on each node
  */
@@ -7,15 +7,16 @@ use crate::document::DocumentData;
 
 use self::encoding_semantic::EncodingSemantics;
 
+pub mod constant_semantics;
 pub mod encoding_semantic;
 mod error_semantic;
 mod missing_semantic;
 pub mod predicate_occurence_semantics;
-mod predicate_semantics;
+pub mod predicate_semantics;
 pub mod special_literal_semantic;
-mod statement_semantic;
-mod syntax;
-mod term_semantic;
+pub mod statement_semantic;
+pub mod syntax;
+pub mod term_semantic;
 
 /**
  * Goes through the tree post order and populates the encoding semantics object in the document
@@ -6,6 +6,10 @@ pub struct PredicateOccurenceSemantics {
     pub node_id: usize,
     pub range: tree_sitter::Range,
     pub location: PredicateOccurenceLocation,
+    /**
+     * If this occurence is an atom, otherwise it is a term with the same name like a function symbol or a constant
+     */
+    pub is_atom: bool,
 }
 
 /**
@@ -16,4 +20,12 @@ pub enum PredicateOccurenceLocation {
     Head,
     Body,
     Condition,
+    /**
+     * In a #show statement, either as a term or as a signature like p/2
+     */
+    Show,
+    /**
+     * In a #defined statement
+     */
+    Defined,
 }
@@ -54,17 +54,92 @@ impl PredicateSemantics {
             .insert((identifier, arity), hash);
     }
 
+    /**
+     * If a statement contains a signature like p/2 returns the identifier, the arity and the range from the identifier to the arity
+     */
+    pub fn get_signature_of_statement(
+        node: &tree_sitter::Node,
+        document: &crate::document::DocumentData,
+    ) -> Option<(String, usize, tree_sitter::Range)> {
+        let mut offset = 1;
+        if node.child(offset)?.kind() == "SUB" {
+            offset += 1;
+        }
+
+        let identifier = node.child(offset)?;
+        let slash = node.child(offset + 1)?;
+        let arity = node.child(offset + 2)?;
+
+        if identifier.kind() != "identifier" || slash.kind() != "SLASH" || arity.kind() != "NUMBER"
+        {
+            return None;
+        }
+
+        Some((
+            document.get_source_for_range(identifier.range()),
+            document
+                .get_source_for_range(arity.range())
+                .parse::<usize>()
+                .ok()?,
+            tree_sitter::Range {
+                start_byte: identifier.start_byte(),
+                end_byte: arity.end_byte(),
+                start_point: identifier.start_position(),
+                end_point: arity.end_position(),
+            },
+        ))
+    }
+
+    /**
+     * Get the name and arity of every predicate that occurs as an atom or in a signature, sorted by name and arity
+     */
+    pub fn get_signatures(&self) -> Vec<(String, usize)> {
+        let mut signatures: Vec<(String, usize)> = self
+            .predicates
+            .iter()
+            .filter(|predicate| predicate.value().iter().any(|occurence| occurence.is_atom))
+            .map(|predicate| predicate.key().clone())
+            .collect();
+        signatures.sort();
+
+        signatures
+    }
+
+    /**
+     * Get every occurence of a predicate in the order they occur in the encoding
+     */
+    pub fn get_occurences(
+        &self,
+        identifier: &str,
+        arity: usize,
+    ) -> Vec<PredicateOccurenceSemantics> {
+        let mut occurences: Vec<PredicateOccurenceSemantics> =
+            match self.predicates.get(&(identifier.to_string(), arity)) {
+                Some(occurences) => occurences.value().iter().cloned().collect(),
+                None => Vec::new(),
+            };
+        occurences.sort_by_key(|occurence| occurence.range.start_byte);
+
+        occurences
+    }
+
     /**
      * Returns the amount of termvecs in this part of the encoding
      */
     pub fn get_predicates_arity_for_node(&self, node: &usize) -> usize {
         if self.predicates_arity.contains_key(node) {
-            return self.predicates_arity.get(node).unwrap().clone();
+            return *self.predicates_arity.get(node).unwrap();
         }
         0
     }
 }
 
+impl Default for PredicateSemantics {
+    fn default() -> Self {
+        PredicateSemantics::new()
+    }
+}
+
 impl Semantics for PredicateSemantics {
     fn on_node(node: tree_sitter::Node, document: &mut crate::document::DocumentData) {
         //Find all predicates with their arity
@@ -89,7 +164,26 @@ impl Semantics for PredicateSemantics {
                     while parent.is_some() {
                         match parent.unwrap().kind() {
                             "bodydot" => location = PredicateOccurenceLocation::Body,
-                            "optcondition" => location = PredicateOccurenceLocation::Condition,
+                            "optcondition" | "optimizecond" => {
+                                location = PredicateOccurenceLocation::Condition
+                            }
+                            // Terms that are shown are not defined by the show statement
+                            "statement"
+                                if location == PredicateOccurenceLocation::Head
+                                    && parent.unwrap().child(0).unwrap().kind() == "SHOW" =>
+                            {
+                                location = PredicateOccurenceLocation::Show;
+                            }
+                            // Heuristics and projections refer to atoms, they do not define them
+                            "statement"
+                                if location == PredicateOccurenceLocation::Head
+                                    && matches!(
+                                        parent.unwrap().child(0).unwrap().kind(),
+                                        "HEURISTIC" | "PROJECT"
+                                    ) =>
+                            {
+                                location = PredicateOccurenceLocation::Body;
+                            }
                             _ => {}
                         }
                         parent = parent.unwrap().parent();
@@ -103,6 +197,31 @@ impl Semantics for PredicateSemantics {
                             node_id: node.id(),
                             range: node.range(),
                             location,
+                            is_atom: node.kind() == "atom",
+                        },
+                    );
+                }
+            }
+            "statement" => {
+                // Find signatures like #show p/2. or #defined p/2.
+                if let Some((identifier, arity, range)) =
+                    Self::get_signature_of_statement(&node, document)
+                {
+                    let location = match node.child(0).unwrap().kind() {
+                        "SHOW" => PredicateOccurenceLocation::Show,
+                        "DEFINED" => PredicateOccurenceLocation::Defined,
+                        _ => return,
+                    };
+
+                    Self::insert_predicate_for_node(
+                        &document.semantics,
+                        identifier,
+                        arity,
+                        PredicateOccurenceSemantics {
+                            node_id: node.id(),
+                            range,
+                            location,
+                            is_atom: true,
                         },
                     );
                 }
@@ -138,3 +257,36 @@ impl Semantics for PredicateSemantics {
         document.semantics.predicate_semantics.predicates_arity = DashMap::new();
     }
 }
+
+#[cfg(test)]
+use crate::test_utils::create_test_document;
+
+#[test]
+fn predicates_should_be_queried_by_signature() {
+    let doc = create_test_document("a(X) :- b(X), c(f(X)). b(1). #show a/1.".to_string());
+    let predicates = &doc.semantics.predicate_semantics;
+
+    // f/1 only occurs as a term
+    assert_eq!(
+        predicates.get_signatures(),
+        vec![
+            ("a".to_string(), 1),
+            ("b".to_string(), 1),
+            ("c".to_string(), 1)
+        ]
+    );
+
+    let locations: Vec<(usize, PredicateOccurenceLocation)> = predicates
+        .get_occurences("b", 1)
+        .into_iter()
+        .map(|occurence| (occurence.range.start_byte, occurence.location))
+        .collect();
+    assert_eq!(
+        locations,
+        vec![
+            (8, PredicateOccurenceLocation::Body),
+            (23, PredicateOccurenceLocation::Head)
+        ]
+    );
+    assert!(predicates.get_occurences("d", 0).is_empty());
+}
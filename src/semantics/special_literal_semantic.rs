@@ -20,6 +20,7 @@ pub enum LiteralType {
 #[derive(Clone, Debug)]
 pub struct SpecialLiteralSemantics {
     pub id: usize,
+    #[allow(dead_code)]
     pub kind: LiteralType,
     pub local_dependency: Vec<(HashSet<String>, HashSet<String>)>,
 }
@@ -29,45 +30,41 @@ impl SpecialLiteralSemantics {
         let mut local_dependency: Vec<(HashSet<String>, HashSet<String>)> = Vec::new();
 
         match node.kind() {
-            "conjunction" | "disjunction" => {
-                if node.child_count() == 3 {
-                    let l0 = node.child(0).unwrap();
-                    let condition = node.child(2).unwrap();
+            "conjunction" | "disjunction" if node.child_count() == 3 => {
+                let l0 = node.child(0).unwrap();
+                let condition = node.child(2).unwrap();
 
-                    local_dependency.push((
-                        HashSet::new(),
-                        document
-                            .semantics
-                            .get_statement_semantics_for_node(l0.id())
-                            .vars,
-                    ));
-                    local_dependency.extend(
-                        document
-                            .semantics
-                            .get_statement_semantics_for_node(condition.id())
-                            .dependencies,
-                    );
-                }
+                local_dependency.push((
+                    HashSet::new(),
+                    document
+                        .semantics
+                        .get_statement_semantics_for_node(l0.id())
+                        .vars,
+                ));
+                local_dependency.extend(
+                    document
+                        .semantics
+                        .get_statement_semantics_for_node(condition.id())
+                        .dependencies,
+                );
             }
-            "bodyaggrelem" => {
-                if node.child_count() >= 2 {
-                    let terms = node.child(0).unwrap();
-                    let condition = node.child(1).unwrap();
+            "bodyaggrelem" if node.child_count() >= 2 => {
+                let terms = node.child(0).unwrap();
+                let condition = node.child(1).unwrap();
 
-                    local_dependency.push((
-                        HashSet::new(),
-                        document
-                            .semantics
-                            .get_statement_semantics_for_node(terms.id())
-                            .vars,
-                    ));
-                    local_dependency.extend(
-                        document
-                            .semantics
-                            .get_statement_semantics_for_node(condition.id())
-                            .dependencies,
-                    );
-                }
+                local_dependency.push((
+                    HashSet::new(),
+                    document
+                        .semantics
+                        .get_statement_semantics_for_node(terms.id())
+                        .vars,
+                ));
+                local_dependency.extend(
+                    document
+                        .semantics
+                        .get_statement_semantics_for_node(condition.id())
+                        .dependencies,
+                );
             }
             _ => {}
         }
@@ -394,7 +394,7 @@ impl StatementSemantics {
     ) {
         let mut dependencies_in_children = Vec::new();
         for child in node.children(&mut node.walk()) {
-            if check_for_node_kind.len() == 0 || check_for_node_kind.contains(child.kind()) {
+            if check_for_node_kind.is_empty() || check_for_node_kind.contains(child.kind()) {
                 dependencies_in_children.extend(
                     document
                         .semantics
@@ -658,55 +658,41 @@ impl StatementSemantics {
      */
     fn check_dependencies(node: Node, document: &mut DocumentData) {
         match node.kind() {
-            "statement" => {
-                if node.child_count() >= 1 {
-                    //If we have one child do the dependency function for a rule (\emptyset, vars(head))
-                    let head = node.child(0).unwrap();
-                    let mut dependencies = vec![(
-                        HashSet::new(),
-                        document
-                            .semantics
-                            .get_statement_semantics_for_node(head.id())
-                            .vars,
-                    )];
-
-                    if (head.kind() == "SHOW" || head.kind() == "EXTERNAL")
-                        && node.child_count() >= 2
-                    {
-                        // We have a show / external statement
-                        let term = node.child(1).unwrap();
-                        let term_semantics = document
-                            .semantics
-                            .get_statement_semantics_for_node(term.id());
+            "statement" if node.child_count() >= 1 => {
+                //If we have one child do the dependency function for a rule (\emptyset, vars(head))
+                let head = node.child(0).unwrap();
+                let mut dependencies = vec![(
+                    HashSet::new(),
+                    document
+                        .semantics
+                        .get_statement_semantics_for_node(head.id())
+                        .vars,
+                )];
+
+                if (head.kind() == "SHOW" || head.kind() == "EXTERNAL")
+                    && node.child_count() >= 2
+                {
+                    // We have a show / external statement
+                    let term = node.child(1).unwrap();
+                    let term_semantics = document
+                        .semantics
+                        .get_statement_semantics_for_node(term.id());
 
-                        // Set variables in the term to dependency
-                        dependencies.push((HashSet::new(), term_semantics.vars.clone()));
+                    // Set variables in the term to dependency
+                    dependencies.push((HashSet::new(), term_semantics.vars.clone()));
 
-                        // Also register variables in this term as that is not handled due to it not being a literal
-                        Self::update_global_vars_for_node(
-                            &document.semantics,
-                            term.id(),
-                            term_semantics.vars.clone(),
-                        );
+                    // Also register variables in this term as that is not handled due to it not being a literal
+                    Self::update_global_vars_for_node(
+                        &document.semantics,
+                        term.id(),
+                        term_semantics.vars.clone(),
+                    );
 
-                        if node.child_count() >= 4 {
-                            // We have a colon and bodydot after the show statement
-                            let body = node.child(3).unwrap();
-                            if node.child(2).unwrap().kind() == "COLON" && body.kind() == "bodydot"
-                            {
-                                dependencies.extend(
-                                    document
-                                        .semantics
-                                        .get_statement_semantics_for_node(body.id())
-                                        .dependencies,
-                                )
-                            }
-                        }
-                    } else if (head.kind() == "IF" || head.kind() == "WIF")
-                        && node.child_count() >= 2
-                    {
-                        let body = node.child(1).unwrap();
-                        if body.kind() == "bodydot" {
+                    if node.child_count() >= 4 {
+                        // We have a colon and bodydot after the show statement
+                        let body = node.child(3).unwrap();
+                        if node.child(2).unwrap().kind() == "COLON" && body.kind() == "bodydot"
+                        {
                             dependencies.extend(
                                 document
                                     .semantics
@@ -714,74 +700,84 @@ impl StatementSemantics {
                                     .dependencies,
                             )
                         }
-
-                        //If we have a weak constraint we need to handle the weight and tuple
-                        if head.kind() == "WIF" {
-                            let weight = node.child(3).unwrap();
-                            let weight_semantics = document
+                    }
+                } else if (head.kind() == "IF" || head.kind() == "WIF")
+                    && node.child_count() >= 2
+                {
+                    let body = node.child(1).unwrap();
+                    if body.kind() == "bodydot" {
+                        dependencies.extend(
+                            document
                                 .semantics
-                                .get_statement_semantics_for_node(weight.id());
+                                .get_statement_semantics_for_node(body.id())
+                                .dependencies,
+                        )
+                    }
 
-                            dependencies.push((HashSet::new(), weight_semantics.vars.clone()));
+                    //If we have a weak constraint we need to handle the weight and tuple
+                    if head.kind() == "WIF" {
+                        let weight = node.child(3).unwrap();
+                        let weight_semantics = document
+                            .semantics
+                            .get_statement_semantics_for_node(weight.id());
+
+                        dependencies.push((HashSet::new(), weight_semantics.vars.clone()));
+                        Self::update_global_vars_for_node(
+                            &document.semantics,
+                            weight.id(),
+                            weight_semantics.vars.clone(),
+                        );
+
+                        if node.child_count() >= 5 {
+                            let tuple = node.child(4).unwrap();
+                            let tuple_semantics = document
+                                .semantics
+                                .get_statement_semantics_for_node(tuple.id());
+                            dependencies.push((HashSet::new(), tuple_semantics.vars.clone()));
                             Self::update_global_vars_for_node(
                                 &document.semantics,
-                                weight.id(),
-                                weight_semantics.vars.clone(),
-                            );
-
-                            if node.child_count() >= 5 {
-                                let tuple = node.child(4).unwrap();
-                                let tuple_semantics = document
-                                    .semantics
-                                    .get_statement_semantics_for_node(tuple.id());
-                                dependencies.push((HashSet::new(), tuple_semantics.vars.clone()));
-                                Self::update_global_vars_for_node(
-                                    &document.semantics,
-                                    tuple.id(),
-                                    tuple_semantics.vars.clone(),
-                                );
-                            }
-                        }
-                    } else if node.child_count() >= 3 {
-                        let body = node.child(2).unwrap();
-                        if body.kind() == "bodydot"
-                            || body.kind() == "maxelemlist"
-                            || body.kind() == "minelemlist"
-                        {
-                            dependencies.extend(
-                                document
-                                    .semantics
-                                    .get_statement_semantics_for_node(body.id())
-                                    .dependencies,
+                                tuple.id(),
+                                tuple_semantics.vars.clone(),
                             );
                         }
                     }
-
-                    Self::update_dependencies_for_node(
-                        &document.semantics,
-                        node.id(),
-                        dependencies,
-                    );
+                } else if node.child_count() >= 3 {
+                    let body = node.child(2).unwrap();
+                    if body.kind() == "bodydot"
+                        || body.kind() == "maxelemlist"
+                        || body.kind() == "minelemlist"
+                    {
+                        dependencies.extend(
+                            document
+                                .semantics
+                                .get_statement_semantics_for_node(body.id())
+                                .dependencies,
+                        );
+                    }
                 }
+
+                Self::update_dependencies_for_node(
+                    &document.semantics,
+                    node.id(),
+                    dependencies,
+                );
             }
-            "atom" => {
-                if node.child_count() >= 3 {
-                    //If we have three children do the dependency function for an atom (pt(t), \emptyset), (\emptyset, dt(t))
-                    let argument = node.child(2).unwrap();
-                    let argument_semantics = document
-                        .semantics
-                        .get_statement_semantics_for_node(argument.id());
-                    let dependencies = vec![
-                        (argument_semantics.provide, HashSet::new()),
-                        (HashSet::new(), argument_semantics.depend),
-                    ];
+            "atom" if node.child_count() >= 3 => {
+                //If we have three children do the dependency function for an atom (pt(t), \emptyset), (\emptyset, dt(t))
+                let argument = node.child(2).unwrap();
+                let argument_semantics = document
+                    .semantics
+                    .get_statement_semantics_for_node(argument.id());
+                let dependencies = vec![
+                    (argument_semantics.provide, HashSet::new()),
+                    (HashSet::new(), argument_semantics.depend),
+                ];
 
-                    Self::update_dependencies_for_node(
-                        &document.semantics,
-                        node.id(),
-                        dependencies,
-                    );
-                }
+                Self::update_dependencies_for_node(
+                    &document.semantics,
+                    node.id(),
+                    dependencies,
+                );
             }
             "literal" => {
                 if node.child_count() == 1 {
@@ -806,54 +802,51 @@ impl StatementSemantics {
                         let operator = node.child(atom_id + 1).unwrap();
                         let right_atom = node.child(atom_id + 2).unwrap();
 
-                        match operator.kind() {
-                            "cmp" => {
-                                // We have an comparison literal, set all variables to a dependency
-                                let mut dep = Vec::new();
-                                let left_semantics = document
-                                    .semantics
-                                    .get_statement_semantics_for_node(atom.id());
-                                let right_semantics = document
-                                    .semantics
-                                    .get_statement_semantics_for_node(right_atom.id());
-
-                                let vars: HashSet<String> = left_semantics
-                                    .vars
-                                    .union(&right_semantics.vars)
-                                    .cloned()
-                                    .collect();
-                                let mut comparison = operator.child(0).unwrap_or(operator).kind();
-                                if node.child_count() >= 4 {
-                                    comparison =
-                                        TermSemantic::negate_comparison_operator(comparison);
-                                }
+                        if operator.kind() == "cmp" {
+                            // We have an comparison literal, set all variables to a dependency
+                            let mut dep = Vec::new();
+                            let left_semantics = document
+                                .semantics
+                                .get_statement_semantics_for_node(atom.id());
+                            let right_semantics = document
+                                .semantics
+                                .get_statement_semantics_for_node(right_atom.id());
 
-                                // If the comparison is not an assignment
-                                if comparison != "EQ" {
-                                    dep.push((HashSet::new(), vars));
-                                } else {
-                                    // (pt(t1), vars(t2))
-                                    dep.push((left_semantics.provide, right_semantics.vars));
-                                    // (pt(t2), vars(t1))
-                                    dep.push((right_semantics.provide, left_semantics.vars));
-                                    // (∅, dt(t1) ∪ dt(t2))
-                                    dep.push((
-                                        HashSet::new(),
-                                        left_semantics
-                                            .depend
-                                            .union(&right_semantics.depend)
-                                            .cloned()
-                                            .collect(),
-                                    ));
-                                }
+                            let vars: HashSet<String> = left_semantics
+                                .vars
+                                .union(&right_semantics.vars)
+                                .cloned()
+                                .collect();
+                            let mut comparison = operator.child(0).unwrap_or(operator).kind();
+                            if node.child_count() >= 4 {
+                                comparison =
+                                    TermSemantic::negate_comparison_operator(comparison);
+                            }
 
-                                Self::update_dependencies_for_node(
-                                    &document.semantics,
-                                    node.id(),
-                                    dep,
-                                );
+                            // If the comparison is not an assignment
+                            if comparison != "EQ" {
+                                dep.push((HashSet::new(), vars));
+                            } else {
+                                // (pt(t1), vars(t2))
+                                dep.push((left_semantics.provide, right_semantics.vars));
+                                // (pt(t2), vars(t1))
+                                dep.push((right_semantics.provide, left_semantics.vars));
+                                // (∅, dt(t1) ∪ dt(t2))
+                                dep.push((
+                                    HashSet::new(),
+                                    left_semantics
+                                        .depend
+                                        .union(&right_semantics.depend)
+                                        .cloned()
+                                        .collect(),
+                                ));
                             }
-                            _ => {}
+
+                            Self::update_dependencies_for_node(
+                                &document.semantics,
+                                node.id(),
+                                dep,
+                            );
                         }
                     } else {
                         let dep = vec![(
@@ -867,9 +860,9 @@ impl StatementSemantics {
                     }
                 }
             }
-            "conjunction" => {
+            "conjunction"
                 // We have a conditional literal
-                if node.child_count() >= 3 {
+                if node.child_count() >= 3 => {
                     let vars = document
                         .semantics
                         .get_statement_semantics_for_node(node.id())
@@ -897,22 +890,9 @@ impl StatementSemantics {
                             .collect(),
                     );
                 }
-            }
-            "bodycomma" => {
+            "bodycomma"
                 // If we only have 2 children we pass on the dependency for the first children (ignore the last child which is a comma)
-                if node.child_count() >= 2 {
-                    let mut hash = HashSet::new();
-                    hash.insert("literal".to_string());
-                    hash.insert("bodycomma".to_string());
-                    hash.insert("bodydot".to_string());
-                    hash.insert("conjunction".to_string());
-                    hash.insert("lubodyaggregate".to_string());
-                    Self::pass_on_dependencies_from_children(node, document, &hash);
-                }
-            }
-            "bodydot" => {
-                if node.child_count() >= 1 {
-                    //If we have at least 1 child pass on the dependencies from each of the literals
+                if node.child_count() >= 2 => {
                     let mut hash = HashSet::new();
                     hash.insert("literal".to_string());
                     hash.insert("bodycomma".to_string());
@@ -921,108 +901,110 @@ impl StatementSemantics {
                     hash.insert("lubodyaggregate".to_string());
                     Self::pass_on_dependencies_from_children(node, document, &hash);
                 }
+            "bodydot" if node.child_count() >= 1 => {
+                //If we have at least 1 child pass on the dependencies from each of the literals
+                let mut hash = HashSet::new();
+                hash.insert("literal".to_string());
+                hash.insert("bodycomma".to_string());
+                hash.insert("bodydot".to_string());
+                hash.insert("conjunction".to_string());
+                hash.insert("lubodyaggregate".to_string());
+                Self::pass_on_dependencies_from_children(node, document, &hash);
             }
             "litvec" | "optcondition" | "optimizelitvec" | "optimizecond" => {
                 Self::pass_on_dependencies_from_children(node, document, &HashSet::new());
             }
-            "lubodyaggregate" => {
-                if node.child_count() >= 2 {
-                    let mut aggregate: Option<Node> = None;
-                    let mut lower_bounds: Option<&str> = None;
-                    let mut lower_bounds_term: Option<Node> = None;
-                    let mut upper_bounds: Option<&str> = None;
-                    let mut upper_bounds_term: Option<Node> = None;
-
-                    // When we have an aggregate with bounds, we need to check where the bounds are
-                    for child in node.children(&mut node.walk()) {
-                        match child.kind() {
-                            "bodyaggregate" => {
-                                aggregate = Some(child);
-                            }
-                            "upper" => {
-                                if child.child_count() >= 2 {
-                                    let cmp = child.child(0).unwrap();
-                                    if cmp.child_count() >= 1 {
-                                        upper_bounds = Some(cmp.child(0).unwrap().kind());
-                                    }
-                                    upper_bounds_term = Some(child.child(1).unwrap());
-                                }
-                            }
-                            "term" => {
-                                lower_bounds_term = Some(child);
-                            }
-                            "cmp" => {
-                                if child.child_count() >= 1 {
-                                    lower_bounds = Some(child.child(0).unwrap().kind());
+            "lubodyaggregate" if node.child_count() >= 2 => {
+                let mut aggregate: Option<Node> = None;
+                let mut lower_bounds: Option<&str> = None;
+                let mut lower_bounds_term: Option<Node> = None;
+                let mut upper_bounds: Option<&str> = None;
+                let mut upper_bounds_term: Option<Node> = None;
+
+                // When we have an aggregate with bounds, we need to check where the bounds are
+                for child in node.children(&mut node.walk()) {
+                    match child.kind() {
+                        "bodyaggregate" => {
+                            aggregate = Some(child);
+                        }
+                        "upper"
+                            if child.child_count() >= 2 => {
+                                let cmp = child.child(0).unwrap();
+                                if cmp.child_count() >= 1 {
+                                    upper_bounds = Some(cmp.child(0).unwrap().kind());
                                 }
+                                upper_bounds_term = Some(child.child(1).unwrap());
                             }
-                            _ => {}
+                        "term" => {
+                            lower_bounds_term = Some(child);
                         }
+                        "cmp"
+                            if child.child_count() >= 1 => {
+                                lower_bounds = Some(child.child(0).unwrap().kind());
+                            }
+                        _ => {}
                     }
+                }
 
-                    let mut dependencies: Vec<(HashSet<String>, HashSet<String>)> = Vec::new();
-                    let mut global_vars = HashSet::new();
+                let mut dependencies: Vec<(HashSet<String>, HashSet<String>)> = Vec::new();
+                let mut global_vars = HashSet::new();
 
-                    //Find out which local and aggregate variables we have
-                    let mut aggr_vars: HashSet<String> = HashSet::new();
-                    if let Some(aggr) = aggregate {
-                        aggr_vars = document
-                            .semantics
-                            .get_statement_semantics_for_node(aggr.id())
-                            .vars;
-                    }
+                //Find out which local and aggregate variables we have
+                let mut aggr_vars: HashSet<String> = HashSet::new();
+                if let Some(aggr) = aggregate {
+                    aggr_vars = document
+                        .semantics
+                        .get_statement_semantics_for_node(aggr.id())
+                        .vars;
+                }
 
-                    //Depending if certain bounds exist and their types we now set the dependencies
-                    if lower_bounds.is_some()
-                        && lower_bounds.unwrap() == "EQ"
-                        && lower_bounds_term.is_some()
-                    {
-                        // If there is an assignment then we need a different global dependency
-                        let lower_bounds_term_semantics = document
-                            .semantics
-                            .get_statement_semantics_for_node(lower_bounds_term.unwrap().id());
-                        dependencies.push((lower_bounds_term_semantics.provide, aggr_vars.clone()));
-                        dependencies.push((HashSet::new(), lower_bounds_term_semantics.depend));
+                //Depending if certain bounds exist and their types we now set the dependencies
+                if let (Some("EQ"), Some(lower_bounds_term)) = (lower_bounds, lower_bounds_term) {
+                    // If there is an assignment then we need a different global dependency
+                    let lower_bounds_term_semantics = document
+                        .semantics
+                        .get_statement_semantics_for_node(lower_bounds_term.id());
+                    dependencies.push((lower_bounds_term_semantics.provide, aggr_vars.clone()));
+                    dependencies.push((HashSet::new(), lower_bounds_term_semantics.depend));
 
-                        global_vars.extend(
-                            document
-                                .semantics
-                                .get_statement_semantics_for_node(lower_bounds_term.unwrap().id())
-                                .vars,
-                        );
-                    }
-                    if upper_bounds.is_some() && upper_bounds.unwrap() == "EQ" {
-                        let upper_bounds_term_semantics = document
+                    global_vars.extend(
+                        document
                             .semantics
-                            .get_statement_semantics_for_node(upper_bounds_term.unwrap().id());
-                        dependencies.push((upper_bounds_term_semantics.provide, aggr_vars));
-                        dependencies.push((HashSet::new(), upper_bounds_term_semantics.depend));
+                            .get_statement_semantics_for_node(lower_bounds_term.id())
+                            .vars,
+                    );
+                }
+                if upper_bounds.is_some() && upper_bounds.unwrap() == "EQ" {
+                    let upper_bounds_term_semantics = document
+                        .semantics
+                        .get_statement_semantics_for_node(upper_bounds_term.unwrap().id());
+                    dependencies.push((upper_bounds_term_semantics.provide, aggr_vars));
+                    dependencies.push((HashSet::new(), upper_bounds_term_semantics.depend));
 
-                        global_vars.extend(
-                            document
-                                .semantics
-                                .get_statement_semantics_for_node(upper_bounds_term.unwrap().id())
-                                .vars,
-                        );
-                    }
-                    if !((lower_bounds.is_some() && lower_bounds.unwrap() == "EQ")
-                        || (upper_bounds.is_some() && upper_bounds.unwrap() == "EQ"))
-                    {
-                        // If there isn't an assignment then all variables will be the dependency for this aggregate
-                        let vars = document
+                    global_vars.extend(
+                        document
                             .semantics
-                            .get_statement_semantics_for_node(node.id())
-                            .vars;
-                        dependencies.push((HashSet::new(), vars));
-                    }
-
-                    Self::update_dependencies_for_node(
-                        &document.semantics,
-                        node.id(),
-                        dependencies,
+                            .get_statement_semantics_for_node(upper_bounds_term.unwrap().id())
+                            .vars,
                     );
-                    Self::update_global_vars_for_node(&document.semantics, node.id(), global_vars);
                 }
+                if !((lower_bounds.is_some() && lower_bounds.unwrap() == "EQ")
+                    || (upper_bounds.is_some() && upper_bounds.unwrap() == "EQ"))
+                {
+                    // If there isn't an assignment then all variables will be the dependency for this aggregate
+                    let vars = document
+                        .semantics
+                        .get_statement_semantics_for_node(node.id())
+                        .vars;
+                    dependencies.push((HashSet::new(), vars));
+                }
+
+                Self::update_dependencies_for_node(
+                    &document.semantics,
+                    node.id(),
+                    dependencies,
+                );
+                Self::update_global_vars_for_node(&document.semantics, node.id(), global_vars);
             }
             "altheadaggrelemvec" => {
                 let mut global_vars = HashSet::new();
@@ -1298,6 +1280,12 @@ impl StatementSemantics {
     }
 }
 
+impl Default for StatementSemantics {
+    fn default() -> Self {
+        StatementSemantics::new()
+    }
+}
+
 impl Semantics for StatementSemantics {
     fn on_node(node: Node, document: &mut DocumentData) {
         StatementSemantics::check_for_variables(node, document);
@@ -28,6 +28,12 @@ impl Syntax {
     }
 }
 
+impl Default for Syntax {
+    fn default() -> Self {
+        Syntax::new()
+    }
+}
+
 impl Semantics for Syntax {
     fn on_node(node: tree_sitter::Node, document: &mut crate::document::DocumentData) {
         if node.is_error() {
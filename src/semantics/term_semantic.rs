@@ -15,7 +15,74 @@ pub enum TermOperator {
     Sub,
     Mul,
     Div,
+    Mod,
+    Pow,
+    And,
+    Or,
+    Xor,
     Dots,
+    Pool,
+    Neg,
+    BitwiseNot,
+    Abs,
+}
+
+impl TermOperator {
+    /**
+     * Get the operator for the node kind of the operation between two terms
+     */
+    pub fn from_binary_operator(kind: &str) -> TermOperator {
+        match kind {
+            "ADD" => TermOperator::Add,
+            "SUB" => TermOperator::Sub,
+            "MUL" => TermOperator::Mul,
+            "SLASH" => TermOperator::Div,
+            // The grammar uses a double backslash for modulo in terms
+            "MOD" | "\\\\" => TermOperator::Mod,
+            "POW" => TermOperator::Pow,
+            "AND" => TermOperator::And,
+            "QUESTION" => TermOperator::Or,
+            "XOR" => TermOperator::Xor,
+            "DOTS" => TermOperator::Dots,
+            _ => TermOperator::None,
+        }
+    }
+
+    /**
+     * The precedence of a binary operator in clingo, operators with a higher precedence bind stronger
+     */
+    pub fn get_precedence(&self) -> u8 {
+        match self {
+            TermOperator::Dots => 1,
+            TermOperator::Xor => 2,
+            TermOperator::Or => 3,
+            TermOperator::And => 4,
+            TermOperator::Add | TermOperator::Sub => 5,
+            TermOperator::Mul | TermOperator::Div | TermOperator::Mod => 6,
+            TermOperator::Pow => 7,
+            _ => 0,
+        }
+    }
+
+    /**
+     * Get the operator for the node kind in front of an unary term
+     */
+    pub fn from_unary_operator(kind: &str) -> TermOperator {
+        match kind {
+            "SUB" => TermOperator::Neg,
+            "BNOT" => TermOperator::BitwiseNot,
+            _ => TermOperator::None,
+        }
+    }
+}
+
+/**
+ * Why an arithmetic operation has no result for some of its values
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArithmeticError {
+    DivisionByZero,
+    Overflow,
 }
 
 /**
@@ -29,6 +96,151 @@ pub enum TermType {
     Variable,
 }
 
+/**
+ * An operand of an operation chain with the unary operators in front of it
+ */
+#[derive(Clone, Debug)]
+pub struct Operand<'a> {
+    pub prefixes: Vec<Node<'a>>,
+    pub term: Node<'a>,
+}
+
+/**
+ * The operations of a term like -1+2*3. The grammar has no precedence and parses this as -((1+2)*3),
+ * so the operands and operators are collected and evaluated with the precedence of clingo instead
+ */
+#[derive(Clone, Debug)]
+pub struct OperationChain<'a> {
+    pub operands: Vec<Operand<'a>>,
+    /**
+     * The binary operators, the operator at index i is between the operands i and i + 1
+     */
+    pub operators: Vec<Node<'a>>,
+}
+
+impl<'a> OperationChain<'a> {
+    /**
+     * Collect the operations of a term or constterm, returns None if the node is not an operation
+     */
+    pub fn from_node(node: Node<'a>) -> Option<OperationChain<'a>> {
+        if !OperationChain::is_operation(node) {
+            return None;
+        }
+
+        let mut chain = OperationChain {
+            operands: Vec::new(),
+            operators: Vec::new(),
+        };
+        chain.collect(node, Vec::new());
+
+        Some(chain)
+    }
+
+    /**
+     * If the node is an unary or binary operation
+     */
+    pub fn is_operation(node: Node) -> bool {
+        if !matches!(node.kind(), "term" | "constterm") {
+            return false;
+        }
+
+        match node.child_count() {
+            2 => !matches!(
+                TermOperator::from_unary_operator(node.child(0).unwrap().kind()),
+                TermOperator::None
+            ),
+            3 => !matches!(
+                TermOperator::from_binary_operator(node.child(1).unwrap().kind()),
+                TermOperator::None
+            ),
+            _ => false,
+        }
+    }
+
+    /**
+     * If the node is an operation that is part of the operation chain of its parent
+     */
+    pub fn is_part_of_chain(node: Node) -> bool {
+        OperationChain::is_operation(node)
+            && node.parent().is_some_and(OperationChain::is_operation)
+    }
+
+    fn collect(&mut self, node: Node<'a>, mut prefixes: Vec<Node<'a>>) {
+        if !OperationChain::is_operation(node) {
+            self.operands.push(Operand {
+                prefixes,
+                term: node,
+            });
+            return;
+        }
+
+        if node.child_count() == 2 {
+            prefixes.push(node.child(0).unwrap());
+            self.collect(node.child(1).unwrap(), prefixes);
+        } else {
+            self.collect(node.child(0).unwrap(), prefixes);
+            self.operators.push(node.child(1).unwrap());
+            self.collect(node.child(2).unwrap(), Vec::new());
+        }
+    }
+
+    /**
+     * Evaluate the chain with the terms of its operands.
+     * Visit is called for every operation with its operator, the terms it is applied to and the result
+     */
+    pub fn evaluate(
+        &self,
+        operands: &[TermSemantic],
+        limit: usize,
+        visit: &mut dyn FnMut(&TermOperator, &[&TermSemantic], &TermSemantic),
+    ) -> TermSemantic {
+        let mut index = 0;
+        self.evaluate_from(&mut index, 0, operands, limit, visit)
+    }
+
+    /**
+     * Evaluate the operations starting at the operand at index, as long as the operators bind at least as strong as the minimum precedence
+     */
+    fn evaluate_from(
+        &self,
+        index: &mut usize,
+        minimum_precedence: u8,
+        operands: &[TermSemantic],
+        limit: usize,
+        visit: &mut dyn FnMut(&TermOperator, &[&TermSemantic], &TermSemantic),
+    ) -> TermSemantic {
+        // Unary operators bind stronger than any binary operator, the innermost is applied first
+        let mut left = operands[*index].clone();
+        for prefix in self.operands[*index].prefixes.iter().rev() {
+            let result = TermSemantic::apply_unary(&left, *prefix);
+            visit(&result.operator, &[&left], &result);
+            left = result;
+        }
+
+        while *index < self.operators.len() {
+            let operator = TermOperator::from_binary_operator(self.operators[*index].kind());
+            let precedence = operator.get_precedence();
+            if precedence < minimum_precedence {
+                break;
+            }
+
+            // Only ** is right associative
+            let next_precedence = match operator {
+                TermOperator::Pow => precedence,
+                _ => precedence + 1,
+            };
+            *index += 1;
+            let right = self.evaluate_from(index, next_precedence, operands, limit, visit);
+
+            let result = TermSemantic::apply_binary(&left, &operator, &right, limit);
+            visit(&operator, &[&left, &right], &result);
+            left = result;
+        }
+
+        left
+    }
+}
+
 /**
  * For a term in a statement this struct contains the information needed to understand what this term is
  */
@@ -36,7 +248,11 @@ pub enum TermType {
 pub struct TermSemantic {
     pub operator: TermOperator,
     pub kind: TermType,
-    pub value: HashSet<usize>,
+    pub value: HashSet<i64>,
+    /**
+     * The term grounds to more values than the interval limit, so value is left empty
+     */
+    pub exceeds_limit: bool,
     pub range: Range,
 }
 
@@ -46,6 +262,7 @@ impl TermSemantic {
             operator: TermOperator::None,
             kind: TermType::Unknown,
             value: HashSet::new(),
+            exceeds_limit: false,
             range: Range {
                 start_byte: 0,
                 end_byte: 0,
@@ -59,22 +276,56 @@ impl TermSemantic {
         let mut kind = TermType::Unknown;
         let mut operator = TermOperator::None;
         let mut value = HashSet::new();
+        let mut exceeds_limit = false;
+        let limit = document.semantics.interval_limit;
 
         match node.kind() {
             "dec" | "NUMBER" => {
                 kind = TermType::Constant;
-                value.insert(
-                    document
-                        .get_source_for_range(node.range())
-                        .parse::<usize>()
-                        .unwrap_or_default(),
-                );
+                // A number that does not fit has no value
+                if let Some(number) =
+                    TermSemantic::parse_number(&document.get_source_for_range(node.range()))
+                {
+                    value.insert(number);
+                }
             }
             "VARIABLE" => kind = TermType::Variable,
-            "identifier" => kind = TermType::Identifier,
+            "identifier" => {
+                kind = TermType::Identifier;
+
+                // A constant declared with #const is replaced by its value
+                if node
+                    .parent()
+                    .is_some_and(|parent| parent.kind() == "term" && parent.child_count() == 1)
+                {
+                    if let Some(constant) = document
+                        .semantics
+                        .constant_semantics
+                        .values
+                        .get(&document.get_source_for_range(node.range()))
+                    {
+                        kind = TermType::Constant;
+                        value = constant.clone();
+                    }
+                }
+            }
             "term" => {
                 // We have a term, find out based on the children what type of term we have
-                if node.child_count() == 1 {
+                if let Some(chain) = OperationChain::from_node(node) {
+                    // Operations are evaluated from their operands, as the parse tree does not respect the precedence
+                    let operands: Vec<TermSemantic> = chain
+                        .operands
+                        .iter()
+                        .map(|operand| {
+                            document
+                                .semantics
+                                .get_statement_semantics_for_node(operand.term.id())
+                                .term
+                        })
+                        .collect();
+
+                    return chain.evaluate(&operands, limit, &mut |_, _, _| {});
+                } else if node.child_count() == 1 {
                     //If we only have one child we pass on the values of that child
                     let child = document
                         .semantics
@@ -83,103 +334,409 @@ impl TermSemantic {
 
                     kind = child.kind.clone();
                     value = child.value.clone();
-                } else if node.child_count() > 2 {
-                    match node.child(1).unwrap().kind() {
-                        //If we have more than 2 children and the second child is an operation
-                        "ADD" => operator = TermOperator::Add,
-                        "SUB" => operator = TermOperator::Sub,
-                        "MUL" => operator = TermOperator::Mul,
-                        "SLASH" => operator = TermOperator::Div,
-                        "DOTS" => operator = TermOperator::Dots,
-                        //Or we have a identfier then we create a new term object
-                        "LPAREN" => {
-                            kind = TermType::Identifier;
-                            return TermSemantic {
-                                operator,
-                                kind,
-                                value,
-                                range: node.range(),
-                            };
+                    exceeds_limit = child.exceeds_limit;
+                } else if let Some(children) = TermSemantic::get_enclosed_terms(node) {
+                    // Absolute values like |X|, parentheses like (X) or pools like (1;2)
+                    let children: Vec<TermSemantic> = children
+                        .iter()
+                        .map(|child| {
+                            document
+                                .semantics
+                                .get_statement_semantics_for_node(child.id())
+                                .term
+                        })
+                        .collect();
+
+                    if children.len() == 1 {
+                        let child = &children[0];
+                        if node.child(0).unwrap().kind() == "VBAR" {
+                            operator = TermOperator::Abs;
                         }
-                        _ => {}
-                    }
-                    //We check what the children are
-                    let left_child = document
-                        .semantics
-                        .get_statement_semantics_for_node(node.child(0).unwrap().id());
-                    let right_child = document
-                        .semantics
-                        .get_statement_semantics_for_node(node.child(2).unwrap().id());
 
-                    if left_child.term.kind == TermType::Constant
-                        && right_child.term.kind == TermType::Constant
-                    {
-                        //If both children are constant the resulting term is constant and we can evaluate the value of the constant
-                        kind = TermType::Constant;
-                        value =
-                            TermSemantic::evaluate(&left_child.term, &right_child.term, &operator);
-                    } else if (left_child.term.kind == TermType::Variable
-                        && right_child.term.kind == TermType::Constant)
-                        || (left_child.term.kind == TermType::Constant
-                            && right_child.term.kind == TermType::Variable)
-                        || (left_child.term.kind == TermType::Variable
-                            && right_child.term.kind == TermType::Variable)
-                    {
-                        kind = TermType::Variable
+                        kind = child.kind.clone();
+                        exceeds_limit = child.exceeds_limit;
+                        value = match operator {
+                            TermOperator::Abs => TermSemantic::evaluate_unary(child, &operator),
+                            _ => child.value.clone(),
+                        };
                     } else {
-                        kind = TermType::Unknown
+                        return TermSemantic::evaluate_pool(&children, node.range(), limit);
                     }
+                } else if node.child_count() > 2 && node.child(1).unwrap().kind() == "LPAREN" {
+                    // Function terms like f(X)
+                    kind = TermType::Identifier;
+                }
+            }
+            "argvec" => {
+                // Pools in the arguments of an atom or function like a(1;2)
+                if let Some(children) = TermSemantic::get_enclosed_terms(node) {
+                    let children: Vec<TermSemantic> = children
+                        .iter()
+                        .map(|child| {
+                            document
+                                .semantics
+                                .get_statement_semantics_for_node(child.id())
+                                .term
+                        })
+                        .collect();
+
+                    return TermSemantic::evaluate_pool(&children, node.range(), limit);
                 }
             }
             _ => {}
         }
 
+        if exceeds_limit {
+            value.clear();
+        }
+
         TermSemantic {
             operator,
             kind,
             value,
+            exceeds_limit,
             range: node.range(),
         }
     }
 
-    pub fn evaluate(a: &TermSemantic, b: &TermSemantic, op: &TermOperator) -> HashSet<usize> {
-        let mut result_set = HashSet::new();
+    /**
+     * Combine the elements of a pool, a pool is only constant if all of its elements are
+     */
+    fn evaluate_pool(children: &[TermSemantic], range: Range, limit: usize) -> TermSemantic {
+        let mut pool = TermSemantic {
+            operator: TermOperator::Pool,
+            range,
+            ..TermSemantic::new()
+        };
 
-        match op {
-            TermOperator::Add => {
-                for s_i in a.value.clone() {
-                    for s_j in b.value.clone() {
-                        result_set.insert(s_i + s_j);
-                    }
+        if children
+            .iter()
+            .all(|child| child.kind == TermType::Constant)
+        {
+            pool.kind = TermType::Constant;
+            for child in children {
+                pool.value.extend(child.value.iter());
+            }
+            pool.exceeds_limit =
+                children.iter().any(|child| child.exceeds_limit) || pool.value.len() > limit;
+        }
+
+        if pool.exceeds_limit {
+            pool.value.clear();
+        }
+
+        pool
+    }
+
+    /**
+     * Apply an unary operation like -X to a term, the operator is the node in front of the term
+     */
+    fn apply_unary(term: &TermSemantic, operator: Node) -> TermSemantic {
+        let op = TermOperator::from_unary_operator(operator.kind());
+        let value = match term.kind {
+            TermType::Constant => TermSemantic::evaluate_unary(term, &op),
+            _ => HashSet::new(),
+        };
+
+        TermSemantic {
+            operator: op,
+            kind: term.kind.clone(),
+            value,
+            exceeds_limit: term.exceeds_limit,
+            range: TermSemantic::get_range_between(operator.range(), term.range),
+        }
+    }
+
+    /**
+     * Apply a binary operation to two terms, the result is only constant if both terms are
+     */
+    fn apply_binary(
+        left: &TermSemantic,
+        op: &TermOperator,
+        right: &TermSemantic,
+        limit: usize,
+    ) -> TermSemantic {
+        let mut result = TermSemantic {
+            operator: op.clone(),
+            range: TermSemantic::get_range_between(left.range, right.range),
+            ..TermSemantic::new()
+        };
+
+        let is_variable =
+            |term: &TermSemantic| matches!(term.kind, TermType::Constant | TermType::Variable);
+
+        if left.kind == TermType::Constant && right.kind == TermType::Constant {
+            //If both children are constant the resulting term is constant and we can evaluate the value of the constant
+            result.kind = TermType::Constant;
+            result.exceeds_limit = left.exceeds_limit || right.exceeds_limit;
+            if !result.exceeds_limit {
+                match TermSemantic::evaluate(left, right, op, limit) {
+                    Some(value) => result.value = value,
+                    None => result.exceeds_limit = true,
                 }
             }
-            TermOperator::Sub => {
-                for s_i in a.value.clone() {
-                    for s_j in b.value.clone() {
-                        result_set.insert(s_i - s_j);
+        } else if is_variable(left) && is_variable(right) {
+            result.kind = TermType::Variable;
+        }
+
+        result
+    }
+
+    fn get_range_between(start: Range, end: Range) -> Range {
+        Range {
+            start_byte: start.start_byte,
+            end_byte: end.end_byte,
+            start_point: start.start_point,
+            end_point: end.end_point,
+        }
+    }
+
+    /**
+     * Evaluate a binary operation for every combination of values, operations that overflow or are undefined (like a division by zero) have no result.
+     * Returns None if the result has more values than the limit
+     */
+    pub fn evaluate(
+        a: &TermSemantic,
+        b: &TermSemantic,
+        op: &TermOperator,
+        limit: usize,
+    ) -> Option<HashSet<i64>> {
+        let mut result_set = HashSet::new();
+
+        for s_i in a.value.iter() {
+            for s_j in b.value.iter() {
+                if let TermOperator::Dots = op {
+                    result_set.extend(TermSemantic::expand_interval(*s_i, *s_j, limit)?);
+                    if result_set.len() > limit {
+                        return None;
                     }
+                    continue;
                 }
-            }
-            TermOperator::Mul => {
-                for s_i in a.value.clone() {
-                    for s_j in b.value.clone() {
-                        result_set.insert(s_i * s_j);
+
+                if let Some(result) = TermSemantic::evaluate_values(*s_i, *s_j, op) {
+                    result_set.insert(result);
+                    if result_set.len() > limit {
+                        return None;
                     }
                 }
             }
-            TermOperator::Div => {
-                for s_i in a.value.clone() {
-                    for s_j in b.value.clone() {
-                        if s_j != 0 {
-                            result_set.insert(s_i / s_j);
+        }
+
+        Some(result_set)
+    }
+
+    /**
+     * Evaluate a binary operation that is not an interval for two values
+     */
+    fn evaluate_values(a: i64, b: i64, op: &TermOperator) -> Option<i64> {
+        match op {
+            TermOperator::Add => a.checked_add(b),
+            TermOperator::Sub => a.checked_sub(b),
+            TermOperator::Mul => a.checked_mul(b),
+            TermOperator::Div => a.checked_div(b),
+            TermOperator::Mod => a.checked_rem(b),
+            TermOperator::Pow => u32::try_from(b)
+                .ok()
+                .and_then(|exponent| a.checked_pow(exponent)),
+            TermOperator::And => Some(a & b),
+            TermOperator::Or => Some(a | b),
+            TermOperator::Xor => Some(a ^ b),
+            _ => None,
+        }
+    }
+
+    /**
+     * Check if a binary operation has no result for some combination of values, because it divides by zero or overflows.
+     * A negative exponent has no result either, but it is not an error
+     */
+    pub fn get_arithmetic_error(
+        a: &HashSet<i64>,
+        b: &HashSet<i64>,
+        op: &TermOperator,
+    ) -> Option<ArithmeticError> {
+        if matches!(op, TermOperator::Div | TermOperator::Mod) && b.contains(&0) {
+            return Some(ArithmeticError::DivisionByZero);
+        }
+
+        if !matches!(
+            op,
+            TermOperator::Add
+                | TermOperator::Sub
+                | TermOperator::Mul
+                | TermOperator::Div
+                | TermOperator::Mod
+                | TermOperator::Pow
+        ) {
+            return None;
+        }
+
+        let overflows = a.iter().any(|s_i| {
+            b.iter().any(|s_j| {
+                let is_defined = !matches!(op, TermOperator::Pow) || *s_j >= 0;
+                is_defined && TermSemantic::evaluate_values(*s_i, *s_j, op).is_none()
+            })
+        });
+
+        if overflows {
+            Some(ArithmeticError::Overflow)
+        } else {
+            None
+        }
+    }
+
+    /**
+     * Check if an unary operation overflows for one of the values
+     */
+    pub fn get_unary_arithmetic_error(
+        a: &HashSet<i64>,
+        op: &TermOperator,
+    ) -> Option<ArithmeticError> {
+        if matches!(op, TermOperator::Neg | TermOperator::Abs) && a.contains(&i64::MIN) {
+            return Some(ArithmeticError::Overflow);
+        }
+
+        None
+    }
+
+    /**
+     * Get all values from start to end, an interval where end is smaller than start is empty.
+     * Returns None if the interval has more values than the limit
+     */
+    pub fn expand_interval(start: i64, end: i64, limit: usize) -> Option<HashSet<i64>> {
+        if end < start {
+            return Some(HashSet::new());
+        }
+
+        // Computed in a wider type, as the size of the interval could overflow
+        if (end as i128 - start as i128 + 1) > limit as i128 {
+            return None;
+        }
+
+        Some((start..=end).collect())
+    }
+
+    /**
+     * Evaluate an unary operation for every value
+     */
+    pub fn evaluate_unary(a: &TermSemantic, op: &TermOperator) -> HashSet<i64> {
+        a.value
+            .iter()
+            .filter_map(|value| match op {
+                TermOperator::Neg => value.checked_neg(),
+                TermOperator::BitwiseNot => Some(!value),
+                TermOperator::Abs => value.checked_abs(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /**
+     * Parse a decimal, hexadecimal, octal or binary number
+     */
+    pub fn parse_number(number: &str) -> Option<i64> {
+        if let Some(hex) = number.strip_prefix("0x") {
+            return i64::from_str_radix(hex, 16).ok();
+        }
+        if let Some(oct) = number.strip_prefix("0o") {
+            return i64::from_str_radix(oct, 8).ok();
+        }
+        if let Some(bin) = number.strip_prefix("0b") {
+            return i64::from_str_radix(bin, 2).ok();
+        }
+
+        number.parse::<i64>().ok()
+    }
+
+    /**
+     * If the term is of the form |X| or (X) return X, for a pool of the form (X;Y) or the arguments of a(X;Y) return all elements
+     */
+    fn get_enclosed_terms(node: Node) -> Option<Vec<Node>> {
+        if node.kind() == "argvec" {
+            return TermSemantic::get_pooled_arguments(node);
+        }
+
+        if node.child_count() != 3 {
+            return None;
+        }
+
+        let inner = node.child(1)?;
+        let termvecs = match (node.child(0)?.kind(), inner.kind()) {
+            ("VBAR", "unaryargvec") => vec![inner],
+            ("LPAREN", "tuplevec") => {
+                // Every element of the pool has to be a tuple with a single element and no trailing comma
+                let mut termvecs = Vec::new();
+                let mut stack = vec![inner];
+                while let Some(current) = stack.pop() {
+                    for child in current.children(&mut current.walk()) {
+                        match child.kind() {
+                            "tuplevec" | "tuplevec_sem" => stack.push(child),
+                            "tuple" if child.child_count() == 1 => termvecs.push(child.child(0)?),
+                            "SEM" => {}
+                            _ => return None,
                         }
                     }
                 }
+                termvecs
             }
-            _ => {}
+            _ => return None,
+        };
+
+        termvecs
+            .into_iter()
+            .map(|termvec| {
+                if !matches!(termvec.kind(), "termvec" | "unaryargvec")
+                    || termvec.child_count() != 1
+                {
+                    return None;
+                }
+                termvec.child(0).filter(|term| term.kind() == "term")
+            })
+            .collect()
+    }
+
+    /**
+     * Get the elements of a pool in the arguments of an atom or function, every element has to be a single term
+     */
+    fn get_pooled_arguments(node: Node) -> Option<Vec<Node>> {
+        let mut cursor = node.walk();
+        if !node
+            .children(&mut cursor)
+            .any(|child| child.kind() == "SEM")
+        {
+            return None;
         }
 
-        result_set
+        let mut terms = Vec::new();
+        for child in node.children(&mut cursor) {
+            match child.kind() {
+                "argvec" => {
+                    terms.extend(TermSemantic::get_pooled_arguments(child).or_else(|| {
+                        TermSemantic::get_single_argument(child).map(|term| vec![term])
+                    })?)
+                }
+                "termvec" => terms.push(TermSemantic::get_single_argument(child)?),
+                "SEM" => {}
+                _ => return None,
+            }
+        }
+
+        Some(terms)
+    }
+
+    /**
+     * If the arguments consist of a single term return it
+     */
+    fn get_single_argument(node: Node) -> Option<Node> {
+        let termvec = match node.kind() {
+            "argvec" if node.child_count() == 1 => node.child(0)?,
+            "termvec" => node,
+            _ => return None,
+        };
+
+        if termvec.kind() != "termvec" || termvec.child_count() != 1 {
+            return None;
+        }
+        termvec.child(0).filter(|term| term.kind() == "term")
     }
 
     /**
@@ -198,10 +755,16 @@ impl TermSemantic {
     }
 }
 
+impl Default for TermSemantic {
+    fn default() -> Self {
+        TermSemantic::new()
+    }
+}
+
 impl Semantics for TermSemantic {
     fn on_node(node: Node, document: &mut DocumentData) {
         match node.kind() {
-            "dec" | "NUMBER" | "term" | "VARIABLE" | "identifier" => {
+            "dec" | "NUMBER" | "term" | "VARIABLE" | "identifier" | "argvec" => {
                 let term = TermSemantic::from_node(node, document);
                 StatementSemantics::update_term_for_node(&document.semantics, node.id(), term);
             }
@@ -0,0 +1,129 @@
+use tower_lsp::lsp_types::{DocumentSymbol, SymbolKind};
+
+use crate::{diagnostics::tree_utils::retrace, document::DocumentData};
+
+use super::{get_statement_name, StatementKind};
+
+/**
+ * Create an outline of the document, with every statement grouped by the kind of statement
+ */
+pub fn check_document_symbols(document: &DocumentData) -> Option<Vec<DocumentSymbol>> {
+    let mut statements: Vec<(StatementKind, DocumentSymbol)> = Vec::new();
+    let mut cursor = document.tree.walk();
+
+    //Look through the tree to find statements, statements do not contain other statements so we do not look inside them
+    let mut reached_root = false;
+    while !reached_root {
+        let node = cursor.node();
+
+        if node.kind() == "statement" {
+            let kind = StatementKind::from_statement(&node);
+            let (name, selection) = get_statement_name(document, node, &kind);
+
+            statements.push((
+                kind.clone(),
+                create_document_symbol(
+                    document,
+                    name,
+                    kind.symbol_kind(),
+                    node.range(),
+                    selection.range(),
+                ),
+            ));
+        } else if cursor.goto_first_child() {
+            continue;
+        }
+
+        if cursor.goto_next_sibling() {
+            continue;
+        }
+
+        (cursor, reached_root) = retrace(cursor);
+    }
+
+    // Group the statements we found by their kind
+    let mut groups = Vec::new();
+    for kind in StatementKind::all() {
+        let children: Vec<DocumentSymbol> = statements
+            .iter()
+            .filter(|(statement_kind, _)| *statement_kind == kind)
+            .map(|(_, symbol)| symbol.clone())
+            .collect();
+
+        if children.is_empty() {
+            continue;
+        }
+
+        let first = children.first().unwrap().range;
+        let last = children.last().unwrap().range;
+
+        #[allow(deprecated)]
+        groups.push(DocumentSymbol {
+            name: kind.group_name().to_string(),
+            detail: Some(format!("{} statements", children.len())),
+            kind: SymbolKind::MODULE,
+            tags: None,
+            deprecated: None,
+            range: tower_lsp::lsp_types::Range::new(first.start, last.end),
+            selection_range: first,
+            children: Some(children),
+        });
+    }
+
+    Some(groups)
+}
+
+/**
+ * Create a document symbol for a part of the encoding
+ */
+fn create_document_symbol(
+    document: &DocumentData,
+    name: String,
+    kind: SymbolKind,
+    range: tree_sitter::Range,
+    selection_range: tree_sitter::Range,
+) -> DocumentSymbol {
+    #[allow(deprecated)]
+    DocumentSymbol {
+        name,
+        detail: None,
+        kind,
+        tags: None,
+        deprecated: None,
+        range: document.convert_range(range),
+        selection_range: document.convert_range(selection_range),
+        children: None,
+    }
+}
+
+#[cfg(test)]
+use crate::test_utils::create_test_document;
+
+#[test]
+fn statements_should_be_grouped_by_kind() {
+    let doc = create_test_document(
+        "#const n=3. edge(1,2). path(X,Y) :- edge(X,Y). :- path(X,X). #show path/2.".to_string(),
+    );
+
+    let symbols = check_document_symbols(&doc).unwrap();
+    let names: Vec<&str> = symbols.iter().map(|group| group.name.as_str()).collect();
+
+    assert_eq!(
+        names,
+        vec!["#const", "Facts", "Rules", "Integrity constraints", "#show"]
+    );
+}
+
+#[test]
+fn statements_should_be_named_by_head_predicate() {
+    let doc = create_test_document("edge(1,2). path(X,Y) :- edge(X,Y). #show path/2.".to_string());
+
+    let symbols = check_document_symbols(&doc).unwrap();
+    let names: Vec<String> = symbols
+        .iter()
+        .flat_map(|group| group.children.clone().unwrap())
+        .map(|symbol| symbol.name)
+        .collect();
+
+    assert_eq!(names, vec!["edge/2", "path/2", "path/2"]);
+}
@@ -0,0 +1,198 @@
+use tower_lsp::lsp_types::SymbolKind;
+use tree_sitter::Node;
+
+use crate::document::DocumentData;
+
+pub mod document_symbol;
+pub mod workspace_symbol;
+
+/**
+ * The kind of statement, used to group statements in the outline
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum StatementKind {
+    Fact,
+    Rule,
+    IntegrityConstraint,
+    WeakConstraint,
+    Show,
+    Optimization,
+    Const,
+    Program,
+    Directive,
+}
+
+impl StatementKind {
+    /**
+     * All statement kinds in the order they should be shown in the outline
+     */
+    pub fn all() -> Vec<StatementKind> {
+        vec![
+            StatementKind::Program,
+            StatementKind::Const,
+            StatementKind::Fact,
+            StatementKind::Rule,
+            StatementKind::IntegrityConstraint,
+            StatementKind::WeakConstraint,
+            StatementKind::Optimization,
+            StatementKind::Show,
+            StatementKind::Directive,
+        ]
+    }
+
+    /**
+     * Find out what kind of statement a statement node is
+     */
+    pub fn from_statement(node: &Node) -> StatementKind {
+        let first = match node.child(0) {
+            Some(first) => first,
+            None => return StatementKind::Directive,
+        };
+
+        match first.kind() {
+            "head" => {
+                if node.child_count() >= 2 && node.child(1).unwrap().kind() == "IF" {
+                    StatementKind::Rule
+                } else {
+                    StatementKind::Fact
+                }
+            }
+            "IF" => StatementKind::IntegrityConstraint,
+            "WIF" => StatementKind::WeakConstraint,
+            "SHOW" => StatementKind::Show,
+            "MINIMIZE" | "MAXIMIZE" => StatementKind::Optimization,
+            "CONST" => StatementKind::Const,
+            "BLOCK" => StatementKind::Program,
+            _ => StatementKind::Directive,
+        }
+    }
+
+    /**
+     * A human readable name for a group of statements of this kind
+     */
+    pub fn group_name(&self) -> &str {
+        match self {
+            StatementKind::Fact => "Facts",
+            StatementKind::Rule => "Rules",
+            StatementKind::IntegrityConstraint => "Integrity constraints",
+            StatementKind::WeakConstraint => "Weak constraints",
+            StatementKind::Show => "#show",
+            StatementKind::Optimization => "#minimize / #maximize",
+            StatementKind::Const => "#const",
+            StatementKind::Program => "#program",
+            StatementKind::Directive => "Directives",
+        }
+    }
+
+    /**
+     * The symbol kind a statement of this kind is shown with
+     */
+    pub fn symbol_kind(&self) -> SymbolKind {
+        match self {
+            StatementKind::Fact => SymbolKind::CONSTANT,
+            StatementKind::Rule => SymbolKind::FUNCTION,
+            StatementKind::IntegrityConstraint => SymbolKind::EVENT,
+            StatementKind::WeakConstraint => SymbolKind::EVENT,
+            StatementKind::Show => SymbolKind::INTERFACE,
+            StatementKind::Optimization => SymbolKind::OPERATOR,
+            StatementKind::Const => SymbolKind::CONSTANT,
+            StatementKind::Program => SymbolKind::NAMESPACE,
+            StatementKind::Directive => SymbolKind::KEY,
+        }
+    }
+}
+
+/**
+ * Find the first predicate in a part of the encoding and return its node together with its identifier and arity
+ */
+pub fn get_first_predicate<'a>(
+    document: &DocumentData,
+    node: Node<'a>,
+) -> Option<(Node<'a>, String, usize)> {
+    let mut cursor = node.walk();
+    let mut stack = vec![node];
+
+    while let Some(current) = stack.pop() {
+        if current.kind() == "atom"
+            && current.child_count() >= 1
+            && current.child(0).unwrap().kind() == "identifier"
+        {
+            let identifier = document.get_source_for_range(current.child(0).unwrap().range());
+            let mut arity = 0;
+            if current.child_count() >= 3 {
+                arity = document
+                    .semantics
+                    .predicate_semantics
+                    .get_predicates_arity_for_node(&current.child(2).unwrap().id())
+                    + 1;
+            }
+            return Some((current, identifier, arity));
+        }
+
+        let children: Vec<Node> = current.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+
+    None
+}
+
+/**
+ * Create a name and the node to select for a statement in the outline
+ */
+pub fn get_statement_name<'a>(
+    document: &DocumentData,
+    node: Node<'a>,
+    kind: &StatementKind,
+) -> (String, Node<'a>) {
+    match kind {
+        StatementKind::Fact | StatementKind::Rule => {
+            if let Some((predicate, identifier, arity)) =
+                get_first_predicate(document, node.child(0).unwrap())
+            {
+                return (format!("{}/{}", identifier, arity), predicate);
+            }
+        }
+        StatementKind::Show => {
+            // A signature like #show p/2.
+            if node.child_count() >= 5 && node.child(2).unwrap().kind() == "SLASH" {
+                return (
+                    format!(
+                        "{}/{}",
+                        document.get_source_for_range(node.child(1).unwrap().range()),
+                        document.get_source_for_range(node.child(3).unwrap().range())
+                    ),
+                    node.child(1).unwrap(),
+                );
+            } else if node.child_count() >= 3 {
+                let term = node.child(1).unwrap();
+                return (
+                    collapse_whitespace(&document.get_source_for_range(term.range())),
+                    term,
+                );
+            }
+        }
+        StatementKind::Const | StatementKind::Program => {
+            if let Some(identifier) = node.child(1) {
+                if identifier.kind() == "identifier" {
+                    return (
+                        document.get_source_for_range(identifier.range()),
+                        identifier,
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+
+    (
+        collapse_whitespace(&document.get_source_for_range(node.range())),
+        node,
+    )
+}
+
+/**
+ * Replace any sequence of whitespace with a single space so the text fits on one line
+ */
+pub fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
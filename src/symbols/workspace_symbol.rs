@@ -0,0 +1,136 @@
+use dashmap::DashMap;
+use tower_lsp::lsp_types::{Location, SymbolInformation, SymbolKind};
+
+use crate::{
+    document::DocumentData, semantics::predicate_occurence_semantics::PredicateOccurenceLocation,
+};
+
+/**
+ * Search all predicates in the known documents that match the query and return where they are defined
+ */
+pub fn check_workspace_symbols(
+    documents: &DashMap<String, DocumentData>,
+    query: &str,
+) -> Option<Vec<SymbolInformation>> {
+    let mut matches: Vec<(usize, SymbolInformation)> = Vec::new();
+
+    for document in documents.iter() {
+        for predicate in document.semantics.predicate_semantics.predicates.iter() {
+            let (identifier, arity) = predicate.key();
+            let name = format!("{}/{}", identifier, arity);
+
+            let score = match fuzzy_match(query, &name) {
+                Some(score) => score,
+                None => continue,
+            };
+
+            // Only point to the places where the predicate is defined, function terms in the head do not define predicates
+            for occurence in predicate.value() {
+                if !occurence.is_atom || occurence.location != PredicateOccurenceLocation::Head {
+                    continue;
+                }
+
+                #[allow(deprecated)]
+                matches.push((
+                    score,
+                    SymbolInformation {
+                        name: name.clone(),
+                        kind: SymbolKind::FUNCTION,
+                        tags: None,
+                        deprecated: None,
+                        location: Location::new(
+                            document.uri.clone(),
+                            document.convert_range(occurence.range),
+                        ),
+                        container_name: None,
+                    },
+                ));
+            }
+        }
+    }
+
+    // Best matches first, then sort by name and position so the result is stable
+    matches.sort_by(|(score_a, a), (score_b, b)| {
+        score_b
+            .cmp(score_a)
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.location.uri.as_str().cmp(b.location.uri.as_str()))
+            .then_with(|| a.location.range.start.cmp(&b.location.range.start))
+    });
+
+    Some(matches.into_iter().map(|(_, symbol)| symbol).collect())
+}
+
+/**
+ * Check if all characters of the query occur in order in the candidate (ignoring case).
+ * Returns a score where a higher score means a better match, consecutive characters and matches at the start score higher
+ */
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<usize> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for query_char in query.to_lowercase().chars() {
+        let found = candidate[position..]
+            .iter()
+            .position(|candidate_char| *candidate_char == query_char)?
+            + position;
+
+        score += 1;
+        if found == 0 {
+            score += 2;
+        }
+        if found > 0 && previous_match == Some(found - 1) {
+            score += 2;
+        }
+
+        previous_match = Some(found);
+        position = found + 1;
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+use crate::test_utils::create_test_document;
+
+#[test]
+fn fuzzy_match_should_require_characters_in_order() {
+    assert!(fuzzy_match("asg3", "assigned/3").is_some());
+    assert!(fuzzy_match("3asg", "assigned/3").is_none());
+    assert!(fuzzy_match("", "assigned/3").is_some());
+    assert!(fuzzy_match("ass", "assigned/3") > fuzzy_match("ass", "pass/1"));
+}
+
+#[test]
+fn workspace_symbols_should_point_to_head_occurences() {
+    let documents = DashMap::new();
+    documents.insert(
+        "file://test.lp".to_string(),
+        create_test_document("assigned(1,2,3). a :- assigned(X,Y,Z), b(X).".to_string()),
+    );
+
+    let symbols = check_workspace_symbols(&documents, "asgn/3").unwrap();
+
+    assert_eq!(symbols.len(), 1);
+    assert_eq!(symbols[0].name, "assigned/3");
+    assert_eq!(symbols[0].location.range.start.character, 0);
+}
+
+#[test]
+fn workspace_symbols_should_skip_function_terms() {
+    let documents = DashMap::new();
+    documents.insert(
+        "file://test.lp".to_string(),
+        create_test_document("p(f(1)). q(g(h(2))).".to_string()),
+    );
+
+    let names: Vec<String> = check_workspace_symbols(&documents, "")
+        .unwrap()
+        .into_iter()
+        .map(|symbol| symbol.name)
+        .collect();
+
+    assert_eq!(names, vec!["p/1", "q/1"]);
+}
@@ -0,0 +1,697 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    sync::RwLock,
+};
+
+use dashmap::{DashMap, DashSet};
+use tower_lsp::lsp_types::{Diagnostic, Url};
+use tree_sitter::Range;
+
+use crate::{
+    configuration::ServerConfiguration,
+    diagnostics::run_diagnostics,
+    document::DocumentData,
+    project::ProjectConfiguration,
+    semantics::constant_semantics::{ConstantSemantics, ExternalConstant},
+};
+
+/**
+ * The documents that are analyzed together, with the projects and the configuration they are analyzed with.
+ * The open documents are kept up to date by their owner, like the client of the language server, the files they include or are grouped with are read from disk
+ */
+#[derive(Default)]
+pub struct Workspace {
+    /**
+     * The open documents and the files they include or are grouped with
+     */
+    pub documents: DashMap<String, DocumentData>,
+    pub open_documents: DashSet<String>,
+    /**
+     * The configuration of the client, the projects can add to it
+     */
+    pub configuration: RwLock<ServerConfiguration>,
+    pub projects: RwLock<Vec<ProjectConfiguration>>,
+}
+
+impl Workspace {
+    pub fn new(configuration: ServerConfiguration) -> Workspace {
+        Workspace {
+            configuration: RwLock::new(configuration),
+            ..Workspace::default()
+        }
+    }
+
+    /**
+     * Get the project a file belongs to, that is the project with the root the file is in
+     */
+    pub fn get_project_of(&self, uri: &Url) -> Option<ProjectConfiguration> {
+        let path = uri.to_file_path().ok()?;
+
+        self.projects
+            .read()
+            .unwrap()
+            .iter()
+            .find(|project| path.starts_with(&project.root))
+            .cloned()
+    }
+
+    /**
+     * Get the configuration for a file, the settings of the client come before the settings of the project
+     */
+    pub fn get_configuration_for(&self, uri: &Url) -> ServerConfiguration {
+        let mut configuration = self.configuration.read().unwrap().clone();
+        if let Some(project) = self.get_project_of(uri) {
+            project.extend_configuration(&mut configuration);
+        }
+
+        configuration
+    }
+
+    /**
+     * Read the project files in the roots of the folders, returns the errors of the project files that are invalid
+     */
+    pub fn load_projects(&self, folders: &[Url]) -> Vec<String> {
+        let mut projects = Vec::new();
+        let mut errors = Vec::new();
+
+        for folder in folders {
+            let path = match folder.to_file_path() {
+                Ok(path) => path,
+                Err(_) => continue,
+            };
+
+            match ProjectConfiguration::load(&path) {
+                Some(Ok(project)) => projects.push(project),
+                Some(Err(error)) => errors.push(error),
+                None => {}
+            }
+        }
+
+        *self.projects.write().unwrap() = projects;
+        errors
+    }
+
+    /**
+     * Store the current state of an open document and load the files it needs.
+     * Returns the document as it is stored, analyzed with the constants of the other documents of its program
+     */
+    pub fn open_document(&self, document: DocumentData) -> DocumentData {
+        let uri = document.uri.to_string();
+
+        // Most changes do not touch the includes, then the files that are needed stay the same
+        let includes = get_include_paths(&document);
+        let is_indexed = self.open_documents.contains(&uri)
+            && self
+                .documents
+                .get(&uri)
+                .is_some_and(|previous| get_include_paths(&previous) == includes);
+
+        self.documents.insert(uri.clone(), document);
+        self.open_documents.insert(uri.clone());
+        if is_indexed {
+            self.update_constants();
+        } else {
+            self.update_index();
+        }
+
+        self.documents.get(&uri).unwrap().clone()
+    }
+
+    /**
+     * Read a file from disk and treat it as an open document, returns false if the file could not be read
+     */
+    pub fn open_file(&self, uri: &Url) -> bool {
+        match load_document(uri, &self.get_configuration_for(uri)) {
+            Some(document) => {
+                self.open_document(document);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /**
+     * Forget a document that is closed, if another document includes it, it is read from disk again
+     */
+    pub fn close_document(&self, uri: &Url) {
+        self.documents.remove(uri.as_str());
+        self.open_documents.remove(uri.as_str());
+        self.update_index();
+    }
+
+    /**
+     * Forget a file that changed on disk, so it is read again when it is needed. Returns false if the file was not known.
+     * Open documents are kept, their changes are sent by their owner
+     */
+    pub fn forget_file(&self, uri: &Url) -> bool {
+        if self.open_documents.contains(uri.as_str()) {
+            return false;
+        }
+
+        self.documents.remove(uri.as_str()).is_some()
+    }
+
+    /**
+     * Get the open documents
+     */
+    pub fn get_open_documents(&self) -> Vec<DocumentData> {
+        self.documents
+            .iter()
+            .filter(|document| self.open_documents.contains(document.key()))
+            .map(|document| document.value().clone())
+            .collect()
+    }
+
+    /**
+     * Analyze every document again with its current configuration and load the files that are needed now
+     */
+    pub fn refresh(&self) {
+        for mut document in self.documents.iter_mut() {
+            let configuration = self.get_configuration_for(&document.uri);
+            configuration.apply(&mut document);
+            document.generate_semantics(None);
+        }
+
+        self.update_index();
+    }
+
+    /**
+     * Run the diagnostics for a document. If the project groups the document with other files, only the files of these groups are part of the program
+     */
+    pub fn diagnose(
+        &self,
+        document: DocumentData,
+        maximum_number_of_problems: u32,
+    ) -> Vec<Diagnostic> {
+        match self.get_project_of(&document.uri) {
+            Some(project) => {
+                project.run_diagnostics(document, &self.documents, maximum_number_of_problems)
+            }
+            None => run_diagnostics(document, &self.documents, maximum_number_of_problems),
+        }
+    }
+
+    /**
+     * Load the files the open documents include or are grounded together with from disk and forget the files that are no longer needed
+     */
+    fn update_index(&self) {
+        let mut documents = self.get_open_documents();
+        let mut needed: HashSet<String> = documents
+            .iter()
+            .map(|document| document.uri.to_string())
+            .collect();
+
+        // The files in the same group of the project form one program
+        let groups: Vec<Url> = documents
+            .iter()
+            .filter_map(|document| {
+                let project = self.get_project_of(&document.uri)?;
+                let files: Vec<Url> = project
+                    .get_groups_of(&document.uri)
+                    .iter()
+                    .flat_map(|group| group.files.clone())
+                    .collect();
+                Some(files)
+            })
+            .flatten()
+            .collect();
+        for uri in groups {
+            if !needed.insert(uri.to_string()) {
+                continue;
+            }
+
+            if !self.documents.contains_key(uri.as_str()) {
+                match load_document(&uri, &self.get_configuration_for(&uri)) {
+                    Some(document) => {
+                        self.documents.insert(uri.to_string(), document);
+                    }
+                    None => continue,
+                }
+            }
+            documents.push(self.documents.get(uri.as_str()).unwrap().clone());
+        }
+
+        for document in &documents {
+            let configuration = self.get_configuration_for(&document.uri);
+            needed.extend(
+                load_included_documents(document, &self.documents, &configuration)
+                    .iter()
+                    .map(|uri| uri.to_string()),
+            );
+        }
+
+        self.documents.retain(|uri, _| needed.contains(uri));
+        self.update_constants();
+    }
+
+    /**
+     * Share the constants declared with #const between the documents of a program.
+     * A document is analyzed again if the constants of the other documents change, until the values do not change anymore
+     */
+    fn update_constants(&self) {
+        for _ in 0..=self.documents.len() {
+            let declared: Vec<(Url, Vec<Include>, HashMap<String, ExternalConstant>)> = self
+                .documents
+                .iter()
+                .map(|document| {
+                    (
+                        document.uri.clone(),
+                        get_includes(&document),
+                        ConstantSemantics::get_exported_constants(&document),
+                    )
+                })
+                .collect();
+
+            let mut changed = false;
+            for (uri, includes, _) in &declared {
+                let included =
+                    collect_included_documents(uri, includes.clone(), &self.documents, None);
+                let mut programs = match self.get_project_of(uri) {
+                    Some(project) => project.get_programs_of(uri, &included, &self.documents),
+                    None => Vec::new(),
+                };
+                if programs.is_empty() {
+                    programs.push(Program::default());
+                }
+
+                // The first declaration in the order of the uris, so the result does not depend on the order of the map
+                let mut others: Vec<&(Url, Vec<Include>, HashMap<String, ExternalConstant>)> =
+                    declared
+                        .iter()
+                        .filter(|(other, _, _)| {
+                            other != uri && programs.iter().any(|program| program.contains(other))
+                        })
+                        .collect();
+                others.sort_by(|(a, _, _), (b, _, _)| a.as_str().cmp(b.as_str()));
+
+                let mut external: HashMap<String, ExternalConstant> = HashMap::new();
+                for (_, _, constants) in others {
+                    for (identifier, constant) in constants {
+                        external
+                            .entry(identifier.clone())
+                            .or_insert_with(|| constant.clone());
+                    }
+                }
+
+                if let Some(mut document) = self.documents.get_mut(uri.as_str()) {
+                    if document.semantics.external_constants != external {
+                        document.semantics.external_constants = external;
+                        document.generate_semantics(None);
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                return;
+            }
+        }
+    }
+}
+
+/**
+ * An #include directive of a document
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Include {
+    /**
+     * The path as it is written, without quotes or angle brackets
+     */
+    pub path: String,
+    /**
+     * The range of the path in the document
+     */
+    pub range: Range,
+    /**
+     * If the file is included with angle brackets like <incmode>, these refer to the libraries of clingo
+     */
+    pub is_system: bool,
+}
+
+/**
+ * The documents that are grounded together as one program, as a view on the known documents
+ */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Program {
+    /**
+     * The uris of the documents in the program, every known document is part of it if this is None
+     */
+    pub members: Option<HashSet<String>>,
+}
+
+impl Program {
+    /**
+     * If the document is part of the program
+     */
+    pub fn contains(&self, uri: &Url) -> bool {
+        self.members
+            .as_ref()
+            .is_none_or(|members| members.contains(uri.as_str()))
+    }
+
+    /**
+     * Visit the document and all other documents of the program.
+     * The document in the map could be outdated, so the current one is visited instead
+     */
+    pub fn for_each_document(
+        &self,
+        document: &DocumentData,
+        documents: &DashMap<String, DocumentData>,
+        mut visit: impl FnMut(&DocumentData),
+    ) {
+        visit(document);
+        for other in documents.iter() {
+            if other.uri != document.uri && self.contains(&other.uri) {
+                visit(other.value());
+            }
+        }
+    }
+}
+
+/**
+ * Find all #include directives in the document
+ */
+pub fn get_includes(document: &DocumentData) -> Vec<Include> {
+    let root = document.tree.root_node();
+    let mut includes = Vec::new();
+
+    for statement in root.children(&mut root.walk()) {
+        if statement.kind() != "statement"
+            || statement.child(0).map(|child| child.kind()) != Some("INCLUDE")
+        {
+            continue;
+        }
+
+        let path = match statement.child(1) {
+            Some(path) => path,
+            None => continue,
+        };
+        match path.kind() {
+            "STRING" if path.child_count() >= 2 => {
+                // The range without the quotes
+                let start = path.child(0).unwrap();
+                let end = path.child(path.child_count() - 1).unwrap();
+                let range = Range {
+                    start_byte: start.end_byte(),
+                    end_byte: end.start_byte(),
+                    start_point: start.end_position(),
+                    end_point: end.start_position(),
+                };
+
+                includes.push(Include {
+                    path: unescape(&document.get_source_for_range(range)),
+                    range,
+                    is_system: false,
+                });
+            }
+            "LT" => {
+                if let Some(identifier) = path.next_sibling().filter(|id| id.kind() == "identifier")
+                {
+                    includes.push(Include {
+                        path: document.get_source_for_range(identifier.range()),
+                        range: identifier.range(),
+                        is_system: true,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    includes
+}
+
+/**
+ * The paths of the #include directives, without their ranges which change with every edit before them
+ */
+fn get_include_paths(document: &DocumentData) -> Vec<(String, bool)> {
+    get_includes(document)
+        .into_iter()
+        .map(|include| (include.path, include.is_system))
+        .collect()
+}
+
+/**
+ * Find the file an include refers to, relative paths are resolved from the directory of the including document.
+ * Returns None for files that can not be found, like the libraries of clingo
+ */
+pub fn resolve_include(uri: &Url, include: &Include) -> Option<Url> {
+    let directory = uri.to_file_path().ok()?.parent()?.to_path_buf();
+    let path = directory.join(&include.path);
+
+    if !path.is_file() {
+        return None;
+    }
+
+    Url::from_file_path(path).ok()
+}
+
+/**
+ * Read and analyze a file that is not opened in the editor
+ */
+pub fn load_document(uri: &Url, configuration: &ServerConfiguration) -> Option<DocumentData> {
+    let source = fs::read_to_string(uri.to_file_path().ok()?).ok()?;
+
+    Some(DocumentData::parse_with_configuration(
+        uri.clone(),
+        &source,
+        configuration,
+    ))
+}
+
+/**
+ * Load every file the document includes directly or indirectly into the documents, files that are already known are not read again.
+ * Returns the uris of all included files
+ */
+pub fn load_included_documents(
+    document: &DocumentData,
+    documents: &DashMap<String, DocumentData>,
+    configuration: &ServerConfiguration,
+) -> Vec<Url> {
+    collect_included_documents(
+        &document.uri,
+        get_includes(document),
+        documents,
+        Some(configuration),
+    )
+}
+
+/**
+ * Find the known files the document includes directly or indirectly, without reading files from disk
+ */
+pub fn get_included_documents(
+    document: &DocumentData,
+    documents: &DashMap<String, DocumentData>,
+) -> Vec<Url> {
+    collect_included_documents(&document.uri, get_includes(document), documents, None)
+}
+
+/**
+ * Follow the includes of a document through the known documents, unknown files are only loaded if there is a configuration to analyze them with
+ */
+fn collect_included_documents(
+    uri: &Url,
+    includes: Vec<Include>,
+    documents: &DashMap<String, DocumentData>,
+    configuration: Option<&ServerConfiguration>,
+) -> Vec<Url> {
+    let mut visited: HashSet<String> = HashSet::from([uri.to_string()]);
+    let mut included = Vec::new();
+    let mut stack: Vec<(Url, Vec<Include>)> = vec![(uri.clone(), includes)];
+
+    while let Some((uri, includes)) = stack.pop() {
+        for include in includes {
+            let target = match resolve_include(&uri, &include) {
+                Some(target) => target,
+                None => continue,
+            };
+            if !visited.insert(target.to_string()) {
+                continue;
+            }
+
+            if !documents.contains_key(target.as_str()) {
+                match configuration.and_then(|configuration| load_document(&target, configuration))
+                {
+                    Some(loaded) => {
+                        documents.insert(target.to_string(), loaded);
+                    }
+                    None => continue,
+                }
+            }
+
+            let target_includes = get_includes(&documents.get(target.as_str()).unwrap());
+            stack.push((target.clone(), target_includes));
+            included.push(target);
+        }
+    }
+
+    included
+}
+
+/**
+ * Unescape the escape sequences of a string in the encoding
+ */
+fn unescape(string: &str) -> String {
+    let mut result = String::with_capacity(string.len());
+    let mut chars = string.chars();
+
+    while let Some(char) = chars.next() {
+        if char != '\\' {
+            result.push(char);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+use crate::test_utils::{create_test_document, TestDirectory};
+
+#[test]
+fn includes_should_be_found() {
+    let doc = create_test_document(
+        "#include \"enc/base.lp\". #include <incmode>. #include \"a\\\"b.lp\".".to_string(),
+    );
+    let includes = get_includes(&doc);
+
+    let paths: Vec<(&str, bool)> = includes
+        .iter()
+        .map(|include| (include.path.as_str(), include.is_system))
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            ("enc/base.lp", false),
+            ("incmode", true),
+            ("a\"b.lp", false)
+        ]
+    );
+    assert_eq!(includes[0].range.start_byte, 10);
+    assert_eq!(includes[0].range.end_byte, 21);
+}
+
+#[test]
+fn included_files_should_be_loaded_from_disk() {
+    let directory = TestDirectory::new();
+    directory.write("enc/base.lp", "#include \"domain.lp\". a :- b.");
+    directory.write("enc/domain.lp", "b. #include \"base.lp\".");
+
+    let mut doc = create_test_document("#include \"enc/base.lp\". #include <incmode>.".to_string());
+    doc.uri = Url::from_file_path(directory.join("main.lp")).unwrap();
+
+    let documents = DashMap::new();
+    let included = load_included_documents(&doc, &documents, &ServerConfiguration::default());
+
+    assert_eq!(included.len(), 2);
+    assert_eq!(documents.len(), 2);
+    assert!(documents.iter().any(|other| other
+        .semantics
+        .predicate_semantics
+        .predicates
+        .contains_key(&("b".to_string(), 0))));
+}
+
+#[test]
+fn closed_documents_should_forget_their_includes() {
+    let directory = TestDirectory::new();
+    directory.write("base.lp", "b.");
+    let main =
+        Url::from_file_path(directory.write("main.lp", "#include \"base.lp\". a :- b.")).unwrap();
+
+    let workspace = Workspace::default();
+    assert!(workspace.open_file(&main));
+    assert_eq!(workspace.documents.len(), 2);
+
+    let document = workspace.get_open_documents().remove(0);
+    assert!(workspace.diagnose(document, 100).is_empty());
+
+    workspace.close_document(&main);
+    assert!(workspace.documents.is_empty());
+}
+
+#[test]
+fn documents_should_only_be_indexed_again_if_their_includes_change() {
+    let directory = TestDirectory::new();
+    directory.write("base.lp", "b.");
+    let main =
+        Url::from_file_path(directory.write("main.lp", "#include \"base.lp\". a :- b.")).unwrap();
+
+    let workspace = Workspace::default();
+    assert!(workspace.open_file(&main));
+    fs::remove_file(directory.join("base.lp")).unwrap();
+
+    // The file that is no longer on disk is kept, as the includes did not change
+    let mut document = create_test_document("#include \"base.lp\". a :- b, c.".to_string());
+    document.uri = main.clone();
+    workspace.open_document(document);
+    assert_eq!(workspace.documents.len(), 2);
+
+    let mut document = create_test_document("#include \"other.lp\". a :- b, c.".to_string());
+    document.uri = main.clone();
+    workspace.open_document(document);
+    assert_eq!(workspace.documents.len(), 1);
+}
+
+#[test]
+fn constants_should_be_resolved_across_the_documents_of_a_program() {
+    let directory = TestDirectory::new();
+    let constants = Url::from_file_path(directory.write("consts.lp", "#const n = 3.")).unwrap();
+    let encoding = Url::from_file_path(directory.write(
+        "enc.lp",
+        "#include \"consts.lp\". a(1..n). b(X) :- X = n+1.",
+    ))
+    .unwrap();
+
+    let workspace = Workspace::default();
+    assert!(workspace.open_file(&encoding));
+
+    // n is used in the including document, so it is not unused where it is declared
+    let document = workspace.documents.get(constants.as_str()).unwrap().clone();
+    assert!(workspace.diagnose(document, 100).is_empty());
+
+    let document = workspace.documents.get(encoding.as_str()).unwrap().clone();
+    assert_eq!(
+        *document
+            .semantics
+            .constant_semantics
+            .values
+            .get("n")
+            .unwrap(),
+        HashSet::from([3])
+    );
+    match crate::hover::check_hover(&document, tower_lsp::lsp_types::Position::new(0, 27))
+        .unwrap()
+        .contents
+    {
+        tower_lsp::lsp_types::HoverContents::Markup(content) => {
+            assert!(content.value.contains("#const n = 3."));
+            assert!(content.value.contains("evaluates to: {3}"));
+        }
+        _ => panic!("expected markdown"),
+    }
+    assert!(workspace.diagnose(document, 100).is_empty());
+
+    // Changing the declaration is seen by the documents that use it
+    let mut document = create_test_document("#const n = 5.".to_string());
+    document.uri = constants.clone();
+    workspace.open_document(document);
+    let document = workspace.documents.get(encoding.as_str()).unwrap().clone();
+    assert_eq!(
+        *document
+            .semantics
+            .constant_semantics
+            .values
+            .get("n")
+            .unwrap(),
+        HashSet::from([5])
+    );
+}